@@ -0,0 +1,35 @@
+//! Automatic sub-agent activation: pick the most relevant configured
+//! agent for a user message based on its declared trigger patterns.
+
+use crate::tools::search::glob_match;
+
+#[derive(Debug, Clone)]
+pub struct AgentDefinition {
+    pub name: String,
+    /// Glob-style patterns matched against the user message and, when
+    /// present, the active file path (e.g. `*.test.*`, `fix*bug*`).
+    pub triggers: Vec<String>,
+    pub priority: i32,
+}
+
+/// Find every configured agent whose triggers match `message` (and
+/// optionally `active_file`), returning them ordered by descending
+/// priority so the caller can auto-activate the top match or present a
+/// short list when several tie.
+pub fn find_matching_agents<'a>(
+    agents: &'a [AgentDefinition],
+    message: &str,
+    active_file: Option<&str>,
+) -> Vec<&'a AgentDefinition> {
+    let mut matches: Vec<&AgentDefinition> = agents
+        .iter()
+        .filter(|agent| {
+            agent.triggers.iter().any(|pattern| {
+                glob_match(pattern, message) || active_file.is_some_and(|f| glob_match(pattern, f))
+            })
+        })
+        .collect();
+
+    matches.sort_by_key(|m| std::cmp::Reverse(m.priority));
+    matches
+}
@@ -0,0 +1,540 @@
+//! The core agent loop: turns a user message into tool calls and model
+//! responses (see `docs/TOOL_CALL_ARCHITECTURE.md`).
+
+pub mod activation;
+pub mod plan;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{AgentConfig, AgentToolsConfig};
+use crate::llm::error::LlmError;
+use crate::llm::model_selector::{Downshift, ModelSelector};
+use crate::llm::models_db::ModelsDb;
+use crate::llm::{LlmProvider, LlmResponse as ProviderResponse, TokenUsage, ToolCallRequest};
+use crate::tools::output_store::ToolOutputStore;
+use crate::tools::registry::{RiskLevel, ToolRegistry};
+use crate::tools::schema::SchemaError;
+
+/// Which set of tools/behaviors the agent operates under for a turn: Ask
+/// is read-only Q&A, Plan produces a step list without executing it, and
+/// Build executes tool calls (subject to approval).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentMode {
+    Ask,
+    Plan,
+    Build,
+}
+
+/// One tool call made (or simulated) during a `ChatAgent::run` turn.
+#[derive(Debug, Clone)]
+pub struct ToolCallRecord {
+    pub name: String,
+    pub args: serde_json::Value,
+    pub result: serde_json::Value,
+    /// `true` if this call was intercepted by dry-run instead of actually
+    /// executing.
+    pub simulated: bool,
+    /// Set when `result` was truncated because it exceeded
+    /// `output_store::TRUNCATE_THRESHOLD_BYTES`; the full output can be
+    /// retrieved with the `fetch_tool_output` tool using this id.
+    pub output_id: Option<String>,
+    /// Wall-clock time the call took to execute. `0` for a simulated
+    /// (dry-run) call.
+    pub duration_ms: u64,
+    /// `false` if the tool returned an error rather than a result; the
+    /// call still produces a record (and a result the model can see) so
+    /// one failing tool doesn't abort the rest of the turn.
+    pub success: bool,
+    /// The tool's error message, set only when `success` is `false`.
+    pub error: Option<String>,
+}
+
+/// Renders a completed call for a channel's tool-activity message: name,
+/// timing, and (on failure) the error — enough to tell at a glance whether
+/// a call succeeded without opening its full result.
+pub fn format_tool_log_for_remote(record: &ToolCallRecord) -> String {
+    let status = if record.success { "ok" } else { "failed" };
+    match &record.error {
+        Some(error) => format!("`{}` ({status}, {}ms): {error}", record.name, record.duration_ms),
+        None => format!("`{}` ({status}, {}ms)", record.name, record.duration_ms),
+    }
+}
+
+/// Builds the JSON metadata attached to a tool-call message for channels
+/// that carry structured metadata alongside text. `error` is omitted
+/// rather than nulled when the call succeeded, so a consumer written
+/// before this field existed sees the same shape it always has.
+pub fn build_metadata_json(record: &ToolCallRecord) -> serde_json::Value {
+    let mut metadata = serde_json::json!({
+        "tool": record.name,
+        "simulated": record.simulated,
+        "duration_ms": record.duration_ms,
+        "success": record.success,
+    });
+    if let Some(error) = &record.error {
+        metadata["error"] = serde_json::json!(error);
+    }
+    metadata
+}
+
+/// Why `ChatAgent::run` stopped iterating, beyond the model simply having
+/// no more tool calls to make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    MaxIterations,
+    MaxToolCalls,
+    MaxDuration,
+}
+
+/// Everything produced by one `ChatAgent::run` turn.
+#[derive(Debug, Clone, Default)]
+pub struct AgentResponse {
+    pub text: String,
+    pub tool_calls: Vec<ToolCallRecord>,
+    /// Human-readable descriptions of the mutating/destructive actions
+    /// that would have run, populated only when dry-run is enabled.
+    pub would_do: Vec<String>,
+    /// Set when a guard (iteration, tool-call, or wall-clock budget)
+    /// stopped the loop before the model was done on its own. Partial
+    /// `text`/`tool_calls` from before the cutoff are still returned.
+    pub stop_reason: Option<StopReason>,
+    /// Which upstream actually served the final turn, for routing
+    /// providers (e.g. OpenRouter); see [`crate::llm::LlmResponse::served_by`].
+    pub served_by: Option<String>,
+    /// Human-readable notes about automatic recovery the loop performed,
+    /// e.g. compacting history after a `ContextTooLong` error. Empty for a
+    /// run that didn't need to recover from anything.
+    pub recovered_from: Vec<String>,
+    /// Token usage summed across every provider call made during this run
+    /// that reported usage. `None` if no call did, including when the run
+    /// made no provider calls at all; see [`crate::usage::apply_usage`].
+    pub usage: Option<TokenUsage>,
+    /// Set when [`ChatAgent::with_model_selector`] is configured and the
+    /// session's spend (as recorded via
+    /// [`ChatAgent::record_spend_usd`]) has crossed `soft_limit_usd`. This
+    /// run already used `provider`, so `downshift.model_id` is the model
+    /// the *next* `ChatAgent` built for this session should be constructed
+    /// with — this agent can't swap its own provider mid-run, since it's
+    /// bound to one at construction like every other provider in this
+    /// crate. `downshift.newly_crossed` is true only the turn the
+    /// threshold was first crossed, so the caller can announce the switch
+    /// once.
+    pub downshift: Option<Downshift>,
+}
+
+/// Drives one conversation: holds the provider and tools used to generate
+/// responses, run plans, and execute tool calls.
+pub struct ChatAgent {
+    provider: Box<dyn LlmProvider>,
+    tools: ToolRegistry,
+    tools_config: AgentToolsConfig,
+    /// Bounds on the agent loop's own behavior (distinct from
+    /// `tools_config`'s per-tool permissions), e.g. how many times a
+    /// schema-invalid tool call is forgiven before it's treated as a hard
+    /// failure; see [`Self::execute_one`].
+    agent_config: AgentConfig,
+    max_iterations: usize,
+    /// Total tool calls allowed across the whole run, distinct from
+    /// `max_iterations` (reasoning turns) since a single turn can request
+    /// several calls at once.
+    max_tool_calls: usize,
+    /// Wall-clock budget for the whole run, checked between tool calls so
+    /// a runaway loop can't burn unbounded time even if each individual
+    /// call is fast.
+    max_duration: Duration,
+    /// When set, mutating/destructive tool calls are intercepted and
+    /// replaced with a synthetic "would execute" result instead of
+    /// actually running, so the full plan of actions can be previewed.
+    /// Read-only tools still execute, since they have no side effects to
+    /// preview around.
+    dry_run: bool,
+    /// Where oversized tool results get spilled so the model can page
+    /// through them later instead of paying to re-send the whole thing
+    /// every turn.
+    output_store: Arc<ToolOutputStore>,
+    /// Maximum number of read-only tool calls from the same turn run
+    /// concurrently. Mutating/destructive calls always run one at a time,
+    /// in order.
+    tool_concurrency: usize,
+    /// Spend-based downshift, configured via
+    /// [`Self::with_model_selector`]. `None` when downshifting isn't wired
+    /// up for this agent.
+    model_selector: Option<ModelSelectorState>,
+}
+
+/// Bundles everything [`ChatAgent::run`] needs to check
+/// [`ModelSelector::maybe_downshift`] each turn: the selector itself, the
+/// models database it validates the downshift target against, the
+/// session this agent belongs to, whether that session's tool calling
+/// needs are non-negotiable, and the running total of what the session
+/// has spent so far (updated by the caller via
+/// [`ChatAgent::record_spend_usd`] once the real cost of each turn is
+/// known from [`crate::usage::apply_usage`]).
+struct ModelSelectorState {
+    selector: Arc<ModelSelector>,
+    models_db: Arc<ModelsDb>,
+    session_id: String,
+    requires_tools: bool,
+    spent_usd: Mutex<f64>,
+}
+
+/// What happened to a single tool call, set aside from the returned
+/// `serde_json::Value` so a simulated (dry-run) result can still record
+/// its human-readable description without re-deriving it from the JSON.
+enum CallOutcome {
+    Simulated { description: String },
+    Ran { result: anyhow::Result<serde_json::Value>, duration_ms: u64 },
+}
+
+impl ChatAgent {
+    pub fn new(provider: Box<dyn LlmProvider>, tools: ToolRegistry, tools_config: AgentToolsConfig) -> Self {
+        Self {
+            provider,
+            tools,
+            tools_config,
+            agent_config: AgentConfig::default(),
+            max_iterations: 25,
+            max_tool_calls: 100,
+            max_duration: Duration::from_secs(600),
+            dry_run: false,
+            output_store: Arc::new(ToolOutputStore::default()),
+            tool_concurrency: 4,
+            model_selector: None,
+        }
+    }
+
+    pub fn with_agent_config(mut self, agent_config: AgentConfig) -> Self {
+        self.agent_config = agent_config;
+        self
+    }
+
+    pub fn with_tool_concurrency(mut self, tool_concurrency: usize) -> Self {
+        self.tool_concurrency = tool_concurrency.max(1);
+        self
+    }
+
+    pub fn with_output_store(mut self, output_store: Arc<ToolOutputStore>) -> Self {
+        self.output_store = output_store;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn with_max_tool_calls(mut self, max_tool_calls: usize) -> Self {
+        self.max_tool_calls = max_tool_calls;
+        self
+    }
+
+    pub fn with_max_duration_secs(mut self, max_duration_secs: u64) -> Self {
+        self.max_duration = Duration::from_secs(max_duration_secs);
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Enables spend-based downshifting for `session_id`: every [`run`](Self::run)
+    /// call checks [`ModelSelector::maybe_downshift`] against spend recorded
+    /// so far via [`Self::record_spend_usd`] and reports the result on
+    /// [`AgentResponse::downshift`]. `requires_tools` should be `true`
+    /// whenever this session relies on tool calling, so a downshift model
+    /// that can't place tool calls is rejected rather than silently
+    /// breaking the session.
+    pub fn with_model_selector(
+        mut self,
+        selector: Arc<ModelSelector>,
+        models_db: Arc<ModelsDb>,
+        session_id: impl Into<String>,
+        requires_tools: bool,
+    ) -> Self {
+        self.model_selector = Some(ModelSelectorState {
+            selector,
+            models_db,
+            session_id: session_id.into(),
+            requires_tools,
+            spent_usd: Mutex::new(0.0),
+        });
+        self
+    }
+
+    /// Adds `amount_usd` to this session's running spend total, so the
+    /// next [`run`](Self::run) call's downshift check reflects it. Callers
+    /// record the real cost here after pricing a completed turn (see
+    /// [`crate::usage::apply_usage`]); `run` itself has no pricing data of
+    /// its own, since [`LlmProvider`] doesn't expose which model served a
+    /// call.
+    pub fn record_spend_usd(&self, amount_usd: f64) {
+        if let Some(state) = &self.model_selector {
+            *state.spent_usd.lock().unwrap() += amount_usd;
+        }
+    }
+
+    /// Runs the reasoning loop for `message`: the model is re-prompted
+    /// with each tool result until it stops requesting tool calls, or one
+    /// of the iteration/tool-call/wall-clock guards trips. A tripped guard
+    /// ends the turn with a "budget exhausted" `stop_reason` rather than
+    /// an error — everything produced up to that point is preserved.
+    pub async fn run(&self, message: &str) -> anyhow::Result<AgentResponse> {
+        let started_at = Instant::now();
+        let mut transcript = message.to_string();
+        let mut response = AgentResponse::default();
+        if let Some(state) = &self.model_selector {
+            let projected_total_usd = *state.spent_usd.lock().unwrap();
+            response.downshift = state.selector.maybe_downshift(
+                &state.session_id,
+                projected_total_usd,
+                state.requires_tools,
+                &state.models_db,
+            )?;
+        }
+        let mut tool_calls_made = 0usize;
+        let mut model_stopped_on_its_own = false;
+        // Tracks, per tool name, how many times a schema-invalid call has
+        // been fed back to the model for correction; shared across
+        // concurrently-executed read-only batches via the mutex.
+        let arg_retry_counts: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
+
+        'turns: for _ in 0..self.max_iterations {
+            let turn = match self.chat_with_recovery(&mut transcript, &mut response.recovered_from).await {
+                Ok(turn) => turn,
+                Err(err) => {
+                    // Render known provider failures (auth, rate limit, ...)
+                    // with their remediation hint instead of propagating a
+                    // raw error the caller would have to know how to format.
+                    response.text = crate::llm::error::render_for_user(&err);
+                    return Ok(response);
+                }
+            };
+            response.text = turn.text.clone();
+            if turn.served_by.is_some() {
+                response.served_by = turn.served_by.clone();
+            }
+            if let Some(turn_usage) = &turn.usage {
+                match &mut response.usage {
+                    Some(usage) => usage.merge(turn_usage),
+                    None => response.usage = Some(*turn_usage),
+                }
+            }
+            if turn.tool_calls.is_empty() {
+                model_stopped_on_its_own = true;
+                break;
+            }
+
+            // Group into maximal runs of consecutive read-only calls (these
+            // can safely run concurrently) separated by mutating/
+            // destructive calls (always run alone, in order), so message
+            // history still reflects the model's original call order.
+            let mut calls = turn.tool_calls.into_iter().peekable();
+            while let Some(first) = calls.next() {
+                let first_is_read_only = self.tools.risk(&first.name) == Some(RiskLevel::ReadOnly);
+                let mut batch = vec![first];
+                if first_is_read_only {
+                    while let Some(next) = calls.peek() {
+                        if self.tools.risk(&next.name) == Some(RiskLevel::ReadOnly) {
+                            batch.push(calls.next().expect("peeked"));
+                        } else {
+                            break;
+                        }
+                    }
+                }
+
+                if started_at.elapsed() >= self.max_duration {
+                    response.stop_reason = Some(StopReason::MaxDuration);
+                    break 'turns;
+                }
+                let remaining_budget = self.max_tool_calls.saturating_sub(tool_calls_made);
+                if remaining_budget == 0 {
+                    response.stop_reason = Some(StopReason::MaxToolCalls);
+                    break 'turns;
+                }
+                let budget_exceeded = batch.len() > remaining_budget;
+                batch.truncate(remaining_budget);
+
+                let outcomes = if batch.len() > 1 {
+                    self.execute_batch_concurrent(batch, &arg_retry_counts).await
+                } else {
+                    let call = batch.into_iter().next().expect("non-empty batch");
+                    vec![self.execute_one(call, &arg_retry_counts).await]
+                };
+
+                for (call, outcome) in outcomes {
+                    tool_calls_made += 1;
+                    let (result, simulated, duration_ms, success, error) = match outcome {
+                        CallOutcome::Simulated { description } => {
+                            response.would_do.push(description.clone());
+                            (serde_json::json!({ "simulated": true, "description": description }), true, 0, true, None)
+                        }
+                        CallOutcome::Ran { result: Ok(value), duration_ms } => (value, false, duration_ms, true, None),
+                        CallOutcome::Ran { result: Err(err), duration_ms } => {
+                            let message = err.to_string();
+                            (serde_json::json!({ "error": message }), false, duration_ms, false, Some(message))
+                        }
+                    };
+
+                    let result_text = result.to_string();
+                    let (preview, output_id) = self.output_store.store_if_large(&result_text)?;
+                    let result_for_record = if output_id.is_some() {
+                        serde_json::json!({ "preview": preview })
+                    } else {
+                        result
+                    };
+
+                    transcript.push_str(&format!("\n[tool result for {}]: {preview}", call.name));
+                    response.tool_calls.push(ToolCallRecord {
+                        name: call.name,
+                        args: call.args,
+                        result: result_for_record,
+                        simulated,
+                        output_id,
+                        duration_ms,
+                        success,
+                        error,
+                    });
+                }
+
+                if budget_exceeded {
+                    response.stop_reason = Some(StopReason::MaxToolCalls);
+                    break 'turns;
+                }
+            }
+        }
+
+        if response.stop_reason.is_none() && !model_stopped_on_its_own {
+            response.stop_reason = Some(StopReason::MaxIterations);
+        }
+
+        if let Some(reason) = response.stop_reason {
+            response.text = format!("{}\n\n[budget exhausted: {reason:?}]", response.text.trim_end());
+        }
+
+        Ok(response)
+    }
+
+    /// Sends `transcript` to the provider. On an `LlmError::ContextTooLong`
+    /// failure, compacts `transcript` once to well under the reported
+    /// limit, records what happened in `recovered_from`, and retries
+    /// exactly once; any other error (including a second failure after
+    /// compaction) is returned as-is.
+    async fn chat_with_recovery(
+        &self,
+        transcript: &mut String,
+        recovered_from: &mut Vec<String>,
+    ) -> anyhow::Result<ProviderResponse> {
+        match self.provider.chat(transcript).await {
+            Ok(turn) => Ok(turn),
+            Err(err) => {
+                let limit = match err.downcast_ref::<LlmError>() {
+                    Some(LlmError::ContextTooLong { limit, .. }) => *limit,
+                    _ => return Err(err),
+                };
+                *transcript = compact_transcript(transcript, limit);
+                recovered_from.push(format!(
+                    "compacted conversation history after a context-length error (provider limit ~{limit} tokens) and retried once"
+                ));
+                self.provider.chat(transcript).await
+            }
+        }
+    }
+
+    /// Runs a single call, intercepting it with a synthetic result if
+    /// dry-run applies, or with a schema-validation error (without ever
+    /// reaching the tool's handler) if its arguments don't match the
+    /// tool's declared `input_schema`. A validation failure is fed back
+    /// to the model as an ordinary failed-call result so it can re-emit
+    /// the call with corrected arguments on its next turn; `arg_retry_counts`
+    /// bounds how many times that's forgiven per tool name before the
+    /// message stops inviting a retry, per `config.agent.max_tool_arg_retries`.
+    async fn execute_one(
+        &self,
+        call: ToolCallRequest,
+        arg_retry_counts: &Mutex<HashMap<String, usize>>,
+    ) -> (ToolCallRequest, CallOutcome) {
+        let is_read_only = self.tools.risk(&call.name) == Some(RiskLevel::ReadOnly);
+        if self.dry_run && !is_read_only {
+            let description = format!("would call `{}` with {}", call.name, call.args);
+            return (call, CallOutcome::Simulated { description });
+        }
+        if let Some(schema) = self.tools.input_schema(&call.name) {
+            if let Err(schema_err) = crate::tools::schema::validate(&call.args, schema) {
+                let attempt = {
+                    let mut counts = arg_retry_counts.lock().expect("arg_retry_counts mutex poisoned");
+                    let count = counts.entry(call.name.clone()).or_insert(0);
+                    *count += 1;
+                    *count
+                };
+                let message = schema_retry_message(&call.name, &schema_err, attempt, self.agent_config.max_tool_arg_retries);
+                return (call, CallOutcome::Ran { result: Err(anyhow::anyhow!(message)), duration_ms: 0 });
+            }
+        }
+        let started = Instant::now();
+        let result = self
+            .tools
+            .call(&call.name, call.args.clone(), CancellationToken::new(), &self.tools_config)
+            .await
+            .map_err(Into::into);
+        let duration_ms = started.elapsed().as_millis() as u64;
+        (call, CallOutcome::Ran { result, duration_ms })
+    }
+
+    /// Runs `batch` (all read-only, by construction at the call site)
+    /// concurrently, bounded by `tool_concurrency`, while preserving the
+    /// original call order in the returned vec so message history stays
+    /// deterministic regardless of which call finishes first.
+    async fn execute_batch_concurrent(
+        &self,
+        batch: Vec<ToolCallRequest>,
+        arg_retry_counts: &Mutex<HashMap<String, usize>>,
+    ) -> Vec<(ToolCallRequest, CallOutcome)> {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.tool_concurrency));
+        let futures = batch.into_iter().map(|call| {
+            let semaphore = semaphore.clone();
+            async move {
+                let _permit = semaphore.acquire().await.expect("semaphore is never closed");
+                self.execute_one(call, arg_retry_counts).await
+            }
+        });
+        futures::future::join_all(futures).await
+    }
+}
+
+/// Renders the error fed back to the model for a schema-invalid tool call.
+/// While `attempt` is within `max_retries`, the message invites a
+/// corrected re-emission; once exhausted, it says so instead, so the model
+/// doesn't keep retrying a call that's already used up its forgiveness.
+fn schema_retry_message(tool_name: &str, err: &SchemaError, attempt: usize, max_retries: usize) -> String {
+    if attempt > max_retries {
+        format!("tool `{tool_name}` arguments are still invalid after {max_retries} retries: {err}")
+    } else {
+        format!(
+            "tool `{tool_name}` arguments are invalid: {err}. Re-emit the call with corrected arguments ({attempt}/{max_retries} retries used)."
+        )
+    }
+}
+
+/// Rough characters-per-token ratio used to size a compaction target
+/// without needing the provider's actual tokenizer.
+const CHARS_PER_TOKEN_ESTIMATE: usize = 4;
+
+/// Drops the oldest part of `transcript`, keeping its tail within well
+/// under `token_limit` (half of it, converted to a character budget) so
+/// the retry has headroom rather than tripping the same error again.
+fn compact_transcript(transcript: &str, token_limit: usize) -> String {
+    let target_chars = (token_limit * CHARS_PER_TOKEN_ESTIMATE) / 2;
+    if transcript.len() <= target_chars {
+        return transcript.to_string();
+    }
+    let mut start = transcript.len() - target_chars;
+    while start < transcript.len() && !transcript.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("[earlier history compacted to fit the provider's context limit]\n{}", &transcript[start..])
+}
@@ -0,0 +1,208 @@
+//! "Plan mode": ask the model for an ordered list of steps up front,
+//! persist it, and let a later Build-mode run execute it one step at a
+//! time (see [`crate::agent::ChatAgent`]).
+
+use crate::agent::ChatAgent;
+use crate::approval::ApprovalChoice;
+use crate::session::storage::TarkStorage;
+
+/// Per-step progress, persisted back into the plan file after every step
+/// so a crashed or interrupted run can resume with `--from-step`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanStepStatus {
+    Pending,
+    Done,
+    Failed,
+    Skipped,
+}
+
+impl PlanStepStatus {
+    fn label(self) -> &'static str {
+        match self {
+            PlanStepStatus::Pending => "pending",
+            PlanStepStatus::Done => "done",
+            PlanStepStatus::Failed => "failed",
+            PlanStepStatus::Skipped => "skipped",
+        }
+    }
+
+    fn parse(label: &str) -> Option<Self> {
+        match label {
+            "pending" => Some(PlanStepStatus::Pending),
+            "done" => Some(PlanStepStatus::Done),
+            "failed" => Some(PlanStepStatus::Failed),
+            "skipped" => Some(PlanStepStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub description: String,
+    /// Names of tools this step is expected to use, so the approval
+    /// system and the user reviewing the plan know what it will touch
+    /// before execution starts.
+    pub tools: Vec<String>,
+    pub status: PlanStepStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub goal: String,
+    pub steps: Vec<PlanStep>,
+}
+
+impl Plan {
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Plan: {}\n\n", self.goal);
+        for (i, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!(
+                "{}. [{}] {}",
+                i + 1,
+                step.status.label(),
+                step.description
+            ));
+            if !step.tools.is_empty() {
+                out.push_str(&format!(" [tools: {}]", step.tools.join(", ")));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Re-parses a plan file previously written by `to_markdown`, so a
+    /// resumed run picks up each step's last recorded status.
+    pub fn from_markdown(markdown: &str) -> Option<Self> {
+        let goal = markdown
+            .lines()
+            .find_map(|line| line.strip_prefix("# Plan: "))?
+            .trim()
+            .to_string();
+        Some(Self::parse_steps(&goal, markdown))
+    }
+
+    /// Parses the model's numbered-list response into a `Plan`. Each line
+    /// is expected to look like `1. [status] <description> [tools: a, b]`,
+    /// with `[status]` optional (defaulting to pending, as a fresh
+    /// model-generated plan has none yet); lines that don't start with a
+    /// number are ignored rather than rejected outright, since models are
+    /// inconsistent about trailing notes.
+    fn parse(goal: &str, model_output: &str) -> Self {
+        Self::parse_steps(goal, model_output)
+    }
+
+    fn parse_steps(goal: &str, text: &str) -> Self {
+        let steps = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+                let mut rest = rest.strip_prefix('.')?.trim();
+                if rest.is_empty() {
+                    return None;
+                }
+
+                let mut status = PlanStepStatus::Pending;
+                if let Some(after_bracket) = rest.strip_prefix('[') {
+                    if let Some(close) = after_bracket.find(']') {
+                        if let Some(parsed) = PlanStepStatus::parse(&after_bracket[..close]) {
+                            status = parsed;
+                            rest = after_bracket[close + 1..].trim();
+                        }
+                    }
+                }
+
+                let (description, tools) = match rest.rfind("[tools:") {
+                    Some(idx) => {
+                        let tools_part = rest[idx + "[tools:".len()..].trim_end_matches(']').trim();
+                        let tools = tools_part
+                            .split(',')
+                            .map(|t| t.trim().to_string())
+                            .filter(|t| !t.is_empty())
+                            .collect();
+                        (rest[..idx].trim().to_string(), tools)
+                    }
+                    None => (rest.to_string(), Vec::new()),
+                };
+                Some(PlanStep {
+                    description,
+                    tools,
+                    status,
+                })
+            })
+            .collect();
+        Plan {
+            goal: goal.to_string(),
+            steps,
+        }
+    }
+}
+
+/// What happened when a single step's work was attempted.
+#[derive(Debug)]
+pub enum StepExecution {
+    Done,
+    Failed(anyhow::Error),
+}
+
+/// Runs a saved plan step by step, checkpointing status back to disk after
+/// every step so a crash or interruption can resume from `from_step`
+/// rather than redoing completed work.
+pub struct PlanExecutor<'a> {
+    storage: &'a TarkStorage,
+}
+
+impl<'a> PlanExecutor<'a> {
+    pub fn new(storage: &'a TarkStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Executes `plan.steps[from_step..]`, calling `approve` before each
+    /// step (honoring the configured trust level) and `execute` to
+    /// perform it. A denied step is recorded as skipped and execution
+    /// continues; a failed step halts the run so the caller can offer
+    /// retry or skip on the next invocation.
+    pub async fn run_from<A, E, F>(&self, plan: &mut Plan, from_step: usize, mut approve: A, mut execute: E) -> anyhow::Result<()>
+    where
+        A: FnMut(&PlanStep) -> ApprovalChoice,
+        E: FnMut(&PlanStep) -> F,
+        F: std::future::Future<Output = StepExecution>,
+    {
+        for idx in from_step..plan.steps.len() {
+            if plan.steps[idx].status == PlanStepStatus::Done {
+                continue;
+            }
+
+            if approve(&plan.steps[idx]) == ApprovalChoice::Deny {
+                plan.steps[idx].status = PlanStepStatus::Skipped;
+            } else {
+                match execute(&plan.steps[idx]).await {
+                    StepExecution::Done => plan.steps[idx].status = PlanStepStatus::Done,
+                    StepExecution::Failed(_) => {
+                        plan.steps[idx].status = PlanStepStatus::Failed;
+                        self.storage.save_plan(plan)?;
+                        return Ok(());
+                    }
+                }
+            }
+            self.storage.save_plan(plan)?;
+        }
+        Ok(())
+    }
+}
+
+impl ChatAgent {
+    /// Asks the model for an ordered plan to achieve `goal`, without
+    /// executing any of it. The caller is expected to persist the result
+    /// via `TarkStorage::save_plan` before switching to Build mode.
+    pub async fn plan(&self, goal: &str) -> anyhow::Result<Plan> {
+        let prompt = format!(
+            "Produce a numbered, ordered plan to accomplish this goal. \
+             For each step give a one-line description and, if it will use \
+             a tool, list it as `[tools: tool_a, tool_b]`.\n\nGoal: {goal}"
+        );
+        let response = self.provider.chat(&prompt).await?;
+        Ok(Plan::parse(goal, &response.text))
+    }
+}
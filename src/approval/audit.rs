@@ -0,0 +1,139 @@
+//! Append-only audit trail of approval decisions, written to
+//! `.tark/audit/` as JSONL so an autonomous run can be reviewed after the
+//! fact: what was approved or denied, under which pattern, by whom.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::approval::{ApprovalChoice, ApprovalPattern, ApprovalRequest, ApprovalResponse};
+
+/// One line of the audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub session_id: String,
+    pub tool: String,
+    pub command: String,
+    pub choice: ApprovalChoice,
+    pub resulting_pattern: Option<ApprovalPattern>,
+    /// Set for approvals/denials that came from a remote channel rather
+    /// than the local CLI.
+    pub user_id: Option<String>,
+}
+
+/// Appends one entry for `request`/`response` to `audit_dir/audit.jsonl`,
+/// creating the directory if needed. `user_id` is `Some` for approvals
+/// made from a remote channel (`RemoteContext::user_id`).
+pub fn record(
+    audit_dir: &Path,
+    request: &ApprovalRequest,
+    response: &ApprovalResponse,
+    timestamp: &str,
+    user_id: Option<String>,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(audit_dir)?;
+    let entry = AuditEntry {
+        timestamp: timestamp.to_string(),
+        session_id: request.session_id.clone(),
+        tool: request.tool.clone(),
+        command: request.command.clone(),
+        choice: response.choice,
+        resulting_pattern: response.pattern.clone(),
+        user_id,
+    };
+    let line = serde_json::to_string(&entry)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(audit_dir))?;
+    writeln!(file, "{line}")
+}
+
+/// Reads the most recent `limit` entries from the audit log, oldest to
+/// newest within that window. Returns an empty list if the log doesn't
+/// exist yet.
+pub fn recent_entries(audit_dir: &Path, limit: usize) -> std::io::Result<Vec<AuditEntry>> {
+    let path = log_path(audit_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let mut entries: Vec<AuditEntry> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    if entries.len() > limit {
+        entries = entries.split_off(entries.len() - limit);
+    }
+    Ok(entries)
+}
+
+fn log_path(audit_dir: &Path) -> PathBuf {
+    audit_dir.join("audit.jsonl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::{ApprovalChoice, ApprovalRequest, ApprovalResponse};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tark-audit-test-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest::for_tool("shell", "echo hi", "session-1", None)
+    }
+
+    #[test]
+    fn recent_entries_on_a_missing_log_returns_empty_instead_of_erroring() {
+        let dir = temp_dir("missing");
+        assert!(recent_entries(&dir, 10).unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn record_then_recent_entries_round_trips_the_decision() {
+        let dir = temp_dir("roundtrip");
+        let response = ApprovalResponse {
+            choice: ApprovalChoice::ApproveOnce,
+            pattern: None,
+        };
+        record(&dir, &request(), &response, "2026-01-01T00:00:00Z", Some("u1".to_string())).unwrap();
+
+        let entries = recent_entries(&dir, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tool, "shell");
+        assert_eq!(entries[0].command, "echo hi");
+        assert_eq!(entries[0].choice, ApprovalChoice::ApproveOnce);
+        assert_eq!(entries[0].user_id.as_deref(), Some("u1"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn recent_entries_keeps_only_the_most_recent_within_the_limit() {
+        let dir = temp_dir("limit");
+        let response = ApprovalResponse {
+            choice: ApprovalChoice::Deny,
+            pattern: None,
+        };
+        for i in 0..5 {
+            record(&dir, &request(), &response, &format!("t{i}"), None).unwrap();
+        }
+
+        let entries = recent_entries(&dir, 2).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, "t3");
+        assert_eq!(entries[1].timestamp, "t4");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
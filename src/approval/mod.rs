@@ -0,0 +1,298 @@
+//! Human-in-the-loop approval for mutating/destructive tool calls: the
+//! request/response types shared by the CLI prompt and the channel
+//! integrations, plus the pattern matcher used to remember a choice for
+//! future calls.
+
+pub mod audit;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tools::search::glob_match;
+
+/// What the user decided about a single approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalChoice {
+    /// Allow this one call and ask again next time.
+    ApproveOnce,
+    /// Allow this call and any matching call for the rest of the session.
+    ApproveSession,
+    /// Allow this call and any matching call permanently (persisted).
+    ApproveAlways,
+    Deny,
+}
+
+/// How an [`ApprovalPattern`]'s `pattern` string is interpreted when
+/// deciding whether it covers a given command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchType {
+    Exact,
+    Prefix,
+    /// `pattern` is a `glob_match`-style pattern (`*`/`?`), anchored to
+    /// the whole command — `cargo test*` matches `cargo test --lib` but
+    /// not `cargo test --lib && rm -rf /`.
+    Glob,
+    /// `pattern` is a regular expression, anchored to the whole command
+    /// at construction time (see [`ApprovalPattern::new`]) so a pattern
+    /// like `rm.*` can't match a safe command that merely contains `rm`
+    /// somewhere in the middle.
+    Regex,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApprovalPatternError {
+    #[error("invalid regex approval pattern `{0}`: {1}")]
+    InvalidRegex(String, regex::Error),
+}
+
+/// A remembered approval, persisted so `ApproveSession`/`ApproveAlways`
+/// choices don't re-prompt for the same command family. Deserializing
+/// goes through [`ApprovalPatternData`] so a pattern loaded from the
+/// audit log runs through the same validation as [`ApprovalPattern::new`]
+/// — a malformed persisted pattern is rejected at load time instead of
+/// silently matching nothing the next time a command is evaluated. The
+/// compiled regex (for `MatchType::Regex`) is built once here rather than
+/// on every [`ApprovalPattern::matches`] call, since that's checked per
+/// tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ApprovalPatternData")]
+pub struct ApprovalPattern {
+    pub pattern: String,
+    pub match_type: MatchType,
+    #[serde(skip)]
+    compiled_regex: Option<Arc<regex::Regex>>,
+}
+
+impl ApprovalPattern {
+    /// Validates `pattern` against `match_type` up front — in particular a
+    /// `MatchType::Regex` pattern must compile — so a malformed pattern is
+    /// rejected at the moment the user grants it rather than silently
+    /// failing to match (or panicking) the next time a command is
+    /// evaluated.
+    pub fn new(pattern: String, match_type: MatchType) -> Result<Self, ApprovalPatternError> {
+        let compiled_regex = if match_type == MatchType::Regex {
+            let regex =
+                anchored_regex(&pattern).map_err(|e| ApprovalPatternError::InvalidRegex(pattern.clone(), e))?;
+            Some(Arc::new(regex))
+        } else {
+            None
+        };
+        Ok(Self {
+            pattern,
+            match_type,
+            compiled_regex,
+        })
+    }
+
+    pub fn matches(&self, command: &str) -> bool {
+        match self.match_type {
+            MatchType::Exact => command == self.pattern,
+            MatchType::Prefix => command.starts_with(&self.pattern),
+            MatchType::Glob => {
+                // `*` spans any characters, so a command that chains another
+                // one on (`;`, `&`, `|`, newline) only matches a pattern that
+                // itself contains such an operator.
+                let chains = |s: &str| s.contains([';', '&', '|', '\n']);
+                glob_match(&self.pattern, command) && (chains(&self.pattern) || !chains(command))
+            }
+            MatchType::Regex => self
+                .compiled_regex
+                .as_ref()
+                .is_some_and(|re| re.is_match(command)),
+        }
+    }
+}
+
+/// Plain serde shape for [`ApprovalPattern`]; deserializing through this
+/// via `#[serde(try_from = ...)]` reuses [`ApprovalPattern::new`]'s
+/// validation for every source a pattern can come from (currently the
+/// audit log), not just callers that remember to call `new` directly.
+#[derive(Debug, Deserialize)]
+pub struct ApprovalPatternData {
+    pub pattern: String,
+    pub match_type: MatchType,
+}
+
+impl TryFrom<ApprovalPatternData> for ApprovalPattern {
+    type Error = ApprovalPatternError;
+
+    fn try_from(data: ApprovalPatternData) -> Result<Self, Self::Error> {
+        ApprovalPattern::new(data.pattern, data.match_type)
+    }
+}
+
+/// Wraps `pattern` so the resulting regex must match the whole command,
+/// not merely a substring of it.
+fn anchored_regex(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    regex::Regex::new(&format!("^(?:{pattern})$"))
+}
+
+/// Byte budget for an [`ApprovalPreview::Diff`] before it's truncated —
+/// large diffs make the approval prompt unreadable and, on remote
+/// channels, risk hitting the message size limit.
+const MAX_PREVIEW_BYTES: usize = 4000;
+
+/// What will actually happen if this request is approved, shown alongside
+/// the bare command/tool name so the user isn't approving blind.
+#[derive(Debug, Clone)]
+pub enum ApprovalPreview {
+    /// A unified diff, e.g. from `apply_patch`.
+    Diff(String),
+    /// The working directory a shell command will run in.
+    WorkingDirectory(String),
+}
+
+impl ApprovalPreview {
+    /// Renders the preview as plain text, truncating a diff that exceeds
+    /// [`MAX_PREVIEW_BYTES`] and noting how much was cut.
+    pub fn render(&self) -> String {
+        match self {
+            ApprovalPreview::Diff(diff) => truncate_preview(diff),
+            ApprovalPreview::WorkingDirectory(dir) => format!("cwd: {dir}"),
+        }
+    }
+}
+
+fn truncate_preview(text: &str) -> String {
+    if text.len() <= MAX_PREVIEW_BYTES {
+        return text.to_string();
+    }
+    let mut end = MAX_PREVIEW_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n… truncated ({} more bytes)",
+        &text[..end],
+        text.len() - end
+    )
+}
+
+/// A tool call awaiting the user's approval.
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    pub tool: String,
+    pub command: String,
+    pub session_id: String,
+    pub preview: Option<ApprovalPreview>,
+    /// The tool's classified risk (see [`crate::tools::tools_db`]), used to
+    /// decide default approval behavior (e.g. auto-approving read-only
+    /// calls) without re-deriving it from the tool name at every call site.
+    pub risk: crate::tools::registry::RiskLevel,
+}
+
+impl ApprovalRequest {
+    /// Builds a request for `tool`, looking up its risk from the central
+    /// [`crate::tools::tools_db`] classification.
+    pub fn for_tool(
+        tool: impl Into<String>,
+        command: impl Into<String>,
+        session_id: impl Into<String>,
+        preview: Option<ApprovalPreview>,
+    ) -> Self {
+        let tool = tool.into();
+        let risk = crate::tools::tools_db::classify(&tool)
+            .map(|c| c.risk)
+            .unwrap_or(crate::tools::registry::RiskLevel::Mutating);
+        Self {
+            tool,
+            command: command.into(),
+            session_id: session_id.into(),
+            preview,
+            risk,
+        }
+    }
+}
+
+/// Renders `request` for a remote channel message: the command plus, when
+/// present, its preview on the following line(s).
+pub fn format_approval_for_remote(request: &ApprovalRequest) -> String {
+    match &request.preview {
+        Some(preview) => format!("`{}`\n```\n{}\n```", request.command, preview.render()),
+        None => format!("`{}`", request.command),
+    }
+}
+
+/// Renders `request` for the interactive CLI prompt, same information as
+/// [`format_approval_for_remote`] without the channel markdown.
+pub fn format_approval_for_cli(request: &ApprovalRequest) -> String {
+    match &request.preview {
+        Some(preview) => format!("{}\n\n{}", request.command, preview.render()),
+        None => request.command.clone(),
+    }
+}
+
+/// The user's decision for an [`ApprovalRequest`], plus the pattern to
+/// remember if the choice was session- or always-scoped.
+#[derive(Debug, Clone)]
+pub struct ApprovalResponse {
+    pub choice: ApprovalChoice,
+    pub pattern: Option<ApprovalPattern>,
+}
+
+/// Identifies who is on the other end of a remote (channel) approval, so
+/// the audit log can attribute the decision to more than just a session
+/// id.
+#[derive(Debug, Clone, Default)]
+pub struct RemoteContext {
+    pub user_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_rejects_invalid_regex() {
+        let err = ApprovalPattern::new("rm(".to_string(), MatchType::Regex).unwrap_err();
+        assert!(matches!(err, ApprovalPatternError::InvalidRegex(_, _)));
+    }
+
+    #[test]
+    fn regex_pattern_is_anchored_to_the_whole_command() {
+        let pattern = ApprovalPattern::new(r"rm foo\.txt".to_string(), MatchType::Regex).unwrap();
+        assert!(pattern.matches("rm foo.txt"));
+        // Without anchoring, `is_match` would find this pattern as a
+        // substring of the longer, appended command below.
+        assert!(!pattern.matches("rm foo.txt && curl evil.example"));
+    }
+
+    #[test]
+    fn exact_prefix_and_glob_still_match_as_before() {
+        let exact = ApprovalPattern::new("cargo test".to_string(), MatchType::Exact).unwrap();
+        assert!(exact.matches("cargo test"));
+        assert!(!exact.matches("cargo test --lib"));
+
+        let prefix = ApprovalPattern::new("cargo test".to_string(), MatchType::Prefix).unwrap();
+        assert!(prefix.matches("cargo test --lib"));
+
+        let glob = ApprovalPattern::new("cargo test*".to_string(), MatchType::Glob).unwrap();
+        assert!(glob.matches("cargo test --lib"));
+        assert!(!glob.matches("cargo build"));
+    }
+
+    #[test]
+    fn deserializing_a_malformed_persisted_pattern_fails_instead_of_matching_nothing() {
+        let json = serde_json::json!({ "pattern": "rm(", "match_type": "regex" });
+        let result: Result<ApprovalPattern, _> = serde_json::from_value(json);
+        assert!(result.is_err(), "a malformed persisted regex pattern should fail to deserialize");
+    }
+
+    #[test]
+    fn deserializing_a_valid_persisted_pattern_matches_correctly() {
+        let json = serde_json::json!({ "pattern": "rm .*", "match_type": "regex" });
+        let pattern: ApprovalPattern = serde_json::from_value(json).unwrap();
+        assert!(pattern.matches("rm foo.txt"));
+    }
+
+    #[test]
+    fn round_trips_through_serialize_and_deserialize() {
+        let pattern = ApprovalPattern::new("rm .*".to_string(), MatchType::Regex).unwrap();
+        let json = serde_json::to_value(&pattern).unwrap();
+        let restored: ApprovalPattern = serde_json::from_value(json).unwrap();
+        assert!(restored.matches("rm foo.txt"));
+    }
+}
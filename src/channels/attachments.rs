@@ -0,0 +1,145 @@
+//! Inbound attachment handling for remote channels: images today, plus
+//! non-image documents (PDF, plain text) that get extracted to text
+//! before being folded into the model context.
+
+use crate::tools::search::glob_match_case;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Image,
+    Pdf,
+    Text,
+    Unsupported,
+}
+
+pub fn classify_attachment(content_type: &str) -> AttachmentKind {
+    match content_type {
+        ct if ct.starts_with("image/") => AttachmentKind::Image,
+        "application/pdf" => AttachmentKind::Pdf,
+        ct if ct.starts_with("text/") => AttachmentKind::Text,
+        _ => AttachmentKind::Unsupported,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub url: String,
+    pub content_type: String,
+}
+
+/// Domains remote attachment URLs are allowed to be fetched from. Slack
+/// and Discord serve attachments from per-workspace CDN hosts, and
+/// generic channels often hand back a pre-signed URL on their own domain,
+/// so entries may be exact hosts or `*.suffix` wildcards.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentAllowlist {
+    patterns: Vec<String>,
+}
+
+impl AttachmentAllowlist {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self { patterns }
+    }
+
+    /// Hosts attachments commonly arrive from: Slack's file CDN, Discord's
+    /// CDN, and common pre-signed-URL object storage hosts used by
+    /// generic webhook channels. Matching is by host only — pre-signed
+    /// URLs carry their auth in the query string, which this allowlist
+    /// intentionally ignores so rotating signatures don't need updates
+    /// here.
+    ///
+    /// S3 is scoped to the virtual-hosted-style signed-URL patterns
+    /// channels actually hand back (`bucket.s3.amazonaws.com`,
+    /// `bucket.s3.<region>.amazonaws.com`) rather than the whole
+    /// `*.amazonaws.com` TLD, which also covers unrelated AWS services
+    /// (EC2, RDS, ...) that have no business serving attachments.
+    pub fn default_allowlist() -> Self {
+        Self::new(vec![
+            "*.slack.com".to_string(),
+            "*.slack-edge.com".to_string(),
+            "cdn.discordapp.com".to_string(),
+            "*.s3.amazonaws.com".to_string(),
+            "*.s3.*.amazonaws.com".to_string(),
+            "*.googleusercontent.com".to_string(),
+            "*.blob.core.windows.net".to_string(),
+        ])
+    }
+
+    /// Extends [`Self::default_allowlist`] with extra host patterns from
+    /// deployment config (`ChannelsConfig::extra_attachment_hosts`) and
+    /// hosts the channel plugin itself advertises as trusted (e.g. a
+    /// self-hosted file store only that plugin knows about).
+    pub fn with_extra_hosts(extra: impl IntoIterator<Item = String>) -> Self {
+        let mut allowlist = Self::default_allowlist();
+        allowlist.patterns.extend(extra);
+        allowlist
+    }
+
+    /// Matching is case-insensitive (hostnames aren't case-significant)
+    /// and uses the same glob engine as [`crate::tools::web_fetch`]'s
+    /// domain allowlist, so a pattern with more than one wildcard segment
+    /// (e.g. `*.s3.*.amazonaws.com`) works the same way in both places.
+    pub fn is_allowed(&self, url: &str) -> bool {
+        let Ok(parsed) = url::Url::parse(url) else {
+            return false;
+        };
+        let Some(host) = parsed.host_str() else {
+            return false;
+        };
+        self.patterns.iter().any(|pattern| glob_match_case(pattern, host, false))
+    }
+}
+
+/// Convert a non-image attachment to plain text the model can read.
+/// PDFs are extracted page-by-page; text files are passed through as-is.
+pub fn extract_text(attachment: &Attachment, raw: &[u8]) -> anyhow::Result<String> {
+    match classify_attachment(&attachment.content_type) {
+        AttachmentKind::Text => Ok(String::from_utf8_lossy(raw).into_owned()),
+        AttachmentKind::Pdf => pdf_extract::extract_text_from_mem(raw).map_err(anyhow::Error::from),
+        AttachmentKind::Image => anyhow::bail!("images are handled as vision input, not text extraction"),
+        AttachmentKind::Unsupported => anyhow::bail!("unsupported attachment type `{}`", attachment.content_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_allowlist_allows_known_hosts() {
+        let allowlist = AttachmentAllowlist::default_allowlist();
+        assert!(allowlist.is_allowed("https://files.slack.com/a.png"));
+        assert!(allowlist.is_allowed("https://cdn.discordapp.com/a.png"));
+        assert!(allowlist.is_allowed("https://lh3.googleusercontent.com/a.png"));
+        assert!(allowlist.is_allowed("https://account.blob.core.windows.net/a.png"));
+    }
+
+    #[test]
+    fn default_allowlist_scopes_s3_to_virtual_hosted_signed_urls() {
+        let allowlist = AttachmentAllowlist::default_allowlist();
+        assert!(allowlist.is_allowed("https://my-bucket.s3.amazonaws.com/a.png"));
+        assert!(allowlist.is_allowed("https://my-bucket.s3.us-east-1.amazonaws.com/a.png"));
+        // Unrelated AWS services must not be swept in by a bare `*.amazonaws.com`.
+        assert!(!allowlist.is_allowed("https://ec2.amazonaws.com/a.png"));
+        assert!(!allowlist.is_allowed("https://some-instance.rds.amazonaws.com/a.png"));
+    }
+
+    #[test]
+    fn is_allowed_rejects_unlisted_hosts() {
+        let allowlist = AttachmentAllowlist::default_allowlist();
+        assert!(!allowlist.is_allowed("https://evil.example/a.png"));
+    }
+
+    #[test]
+    fn is_allowed_matches_case_insensitively() {
+        let allowlist = AttachmentAllowlist::new(vec!["*.slack.com".to_string()]);
+        assert!(allowlist.is_allowed("https://FILES.SLACK.COM/a.png"));
+    }
+
+    #[test]
+    fn with_extra_hosts_extends_the_default_allowlist() {
+        let allowlist = AttachmentAllowlist::with_extra_hosts(vec!["files.example.com".to_string()]);
+        assert!(allowlist.is_allowed("https://files.example.com/a.png"));
+        assert!(allowlist.is_allowed("https://files.slack.com/a.png"));
+    }
+}
@@ -0,0 +1,104 @@
+//! Inbound message deduplication. Channel webhooks (Slack retries in
+//! particular) can redeliver the same event; track recently seen IDs and
+//! drop repeats.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Default)]
+pub struct DedupeStats {
+    pub seen: u64,
+    pub duplicates_dropped: u64,
+    pub evicted_expired: u64,
+}
+
+/// Tracks recently seen inbound message IDs within a TTL window, capped at
+/// `max_entries` (oldest evicted first once full).
+pub struct InboundDedupe {
+    ttl: Duration,
+    max_entries: usize,
+    seen: HashMap<String, Instant>,
+    stats: DedupeStats,
+}
+
+impl InboundDedupe {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries,
+            seen: HashMap::new(),
+            stats: DedupeStats::default(),
+        }
+    }
+
+    /// Returns `true` if `message_id` has already been seen within the TTL
+    /// window (i.e. it should be dropped); records it as seen either way.
+    pub fn is_duplicate(&mut self, message_id: &str) -> bool {
+        self.evict_expired();
+        self.stats.seen += 1;
+
+        if self.seen.contains_key(message_id) {
+            self.stats.duplicates_dropped += 1;
+            return true;
+        }
+
+        if self.seen.len() >= self.max_entries {
+            if let Some(oldest_key) = self
+                .seen
+                .iter()
+                .min_by_key(|(_, inserted_at)| **inserted_at)
+                .map(|(key, _)| key.clone())
+            {
+                self.seen.remove(&oldest_key);
+            }
+        }
+        self.seen.insert(message_id.to_string(), Instant::now());
+        false
+    }
+
+    fn evict_expired(&mut self) {
+        let ttl = self.ttl;
+        let before = self.seen.len();
+        self.seen.retain(|_, inserted_at| inserted_at.elapsed() < ttl);
+        self.stats.evicted_expired += (before - self.seen.len()) as u64;
+    }
+
+    pub fn stats(&self) -> &DedupeStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_delivery_of_the_same_id_is_flagged_as_a_duplicate() {
+        let mut dedupe = InboundDedupe::new(Duration::from_secs(60), 100);
+        assert!(!dedupe.is_duplicate("msg-1"));
+        assert!(dedupe.is_duplicate("msg-1"));
+        assert_eq!(dedupe.stats().duplicates_dropped, 1);
+        assert_eq!(dedupe.stats().seen, 2);
+    }
+
+    #[test]
+    fn a_message_id_expires_after_the_ttl_window() {
+        let mut dedupe = InboundDedupe::new(Duration::from_millis(10), 100);
+        assert!(!dedupe.is_duplicate("msg-1"));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!dedupe.is_duplicate("msg-1"));
+        assert_eq!(dedupe.stats().evicted_expired, 1);
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted_once_max_entries_is_reached() {
+        let mut dedupe = InboundDedupe::new(Duration::from_secs(60), 2);
+        dedupe.is_duplicate("first");
+        dedupe.is_duplicate("second");
+        dedupe.is_duplicate("third");
+
+        // "first" should have been evicted to make room for "third", so it
+        // reads as new again rather than a duplicate.
+        assert!(!dedupe.is_duplicate("first"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Long-lived websocket bridge for channel plugins that need a persistent
+//! connection (Discord's gateway, and similar) rather than the poll or
+//! webhook paths: connects, feeds received frames into the plugin's
+//! `handle_gateway_event`, and reconnects with backoff if the connection
+//! drops.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio_util::sync::CancellationToken;
+
+/// Where to connect and how often to heartbeat, as provided by the
+/// channel plugin for its specific gateway protocol.
+#[derive(Debug, Clone)]
+pub struct GatewayConnectionInfo {
+    pub url: String,
+    pub heartbeat_interval: Duration,
+}
+
+/// A single frame received from (or sent to) the gateway socket. Kept as
+/// raw text since each plugin's gateway protocol (Discord's JSON opcodes,
+/// etc.) parses it differently.
+pub type GatewayFrame = String;
+
+/// Abstracts the actual websocket transport so this module doesn't need a
+/// concrete client dependency; the binary embedding tark implements this
+/// over its websocket client of choice.
+#[async_trait]
+pub trait GatewaySocket: Send {
+    /// Waits for the next frame, or `None` once the connection closes.
+    async fn recv(&mut self) -> Option<GatewayFrame>;
+    async fn send_heartbeat(&mut self) -> anyhow::Result<()>;
+}
+
+/// Opens a fresh socket for `info`. Implemented alongside [`GatewaySocket`].
+#[async_trait]
+pub trait GatewayConnector: Send + Sync {
+    async fn connect(&self, info: &GatewayConnectionInfo) -> anyhow::Result<Box<dyn GatewaySocket>>;
+}
+
+/// Receives gateway frames for one channel plugin. Implemented by the
+/// plugin wrapper that exposes `channel_handle_gateway_event`.
+#[async_trait]
+pub trait GatewayPlugin: Send + Sync {
+    fn connection_info(&self) -> GatewayConnectionInfo;
+    async fn handle_gateway_event(&self, frame: GatewayFrame) -> anyhow::Result<()>;
+}
+
+/// Doubles the reconnect delay after each failed attempt, capped at `max`,
+/// and resets to `base` once a connection is established.
+#[derive(Debug, Clone)]
+struct Backoff {
+    base: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, current: base }
+    }
+
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+        self.current = (self.current * 2).min(self.max);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.current = self.base;
+    }
+}
+
+/// Runs the reconnect loop for `plugin` until `shutdown` is cancelled:
+/// connects via `connector`, feeds every received frame into
+/// `plugin.handle_gateway_event`, sends heartbeats on `info`'s interval,
+/// and reconnects with exponential backoff if the connection drops or
+/// fails to establish.
+pub async fn run_gateway(connector: &dyn GatewayConnector, plugin: &dyn GatewayPlugin, shutdown: CancellationToken) {
+    let info = plugin.connection_info();
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+
+    while !shutdown.is_cancelled() {
+        let mut socket = match connector.connect(&info).await {
+            Ok(socket) => socket,
+            Err(_) => {
+                let delay = backoff.next_delay();
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => continue,
+                    _ = shutdown.cancelled() => break,
+                }
+            }
+        };
+        backoff.reset();
+
+        let mut heartbeat = tokio::time::interval(info.heartbeat_interval);
+        loop {
+            tokio::select! {
+                frame = socket.recv() => match frame {
+                    Some(frame) => {
+                        let _ = plugin.handle_gateway_event(frame).await;
+                    }
+                    None => break,
+                },
+                _ = heartbeat.tick() => {
+                    if socket.send_heartbeat().await.is_err() {
+                        break;
+                    }
+                }
+                _ = shutdown.cancelled() => return,
+            }
+        }
+
+        if shutdown.is_cancelled() {
+            break;
+        }
+        let delay = backoff.next_delay();
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.cancelled() => break,
+        }
+    }
+}
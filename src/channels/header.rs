@@ -0,0 +1,14 @@
+//! Renders the header posted at the start of a channel session — the
+//! first thing a user sees in a Slack/Discord thread tark is driving.
+//! Timestamps in the header go through `config.display.timezone`, same as
+//! CLI output, while the session itself is timestamped in UTC internally.
+
+use chrono::{DateTime, Utc};
+
+use crate::core::timezone::{format_timestamp, TimezoneChoice};
+
+/// Renders `"### {session_name}\nstarted {rendered timestamp}"`, the
+/// standard header posted once per channel session.
+pub fn format_session_header(session_name: &str, started_at: DateTime<Utc>, zone: &TimezoneChoice) -> String {
+    format!("### {session_name}\nstarted {}", format_timestamp(started_at, zone))
+}
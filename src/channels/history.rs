@@ -0,0 +1,68 @@
+//! Bounded per-conversation message history for remote channels (Slack,
+//! Discord, ...). Keeps memory and per-turn context cost predictable on
+//! long-lived channel threads.
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct ChannelMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// A message history capped at `max_messages`, dropping the oldest
+/// message once the cap is exceeded.
+#[derive(Debug)]
+pub struct BoundedHistory {
+    messages: VecDeque<ChannelMessage>,
+    max_messages: usize,
+    max_estimated_tokens: Option<usize>,
+}
+
+impl BoundedHistory {
+    pub fn new(max_messages: usize) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            max_messages,
+            max_estimated_tokens: None,
+        }
+    }
+
+    /// Also cap by a rough token estimate, so a remote session with a
+    /// handful of very long messages doesn't blow past the model's
+    /// context window even while under `max_messages`.
+    pub fn with_token_cap(mut self, max_estimated_tokens: usize) -> Self {
+        self.max_estimated_tokens = Some(max_estimated_tokens);
+        self
+    }
+
+    pub fn push(&mut self, message: ChannelMessage) {
+        self.messages.push_back(message);
+        while self.messages.len() > self.max_messages {
+            self.messages.pop_front();
+        }
+        if let Some(max_tokens) = self.max_estimated_tokens {
+            while self.estimated_tokens() > max_tokens && self.messages.len() > 1 {
+                self.messages.pop_front();
+            }
+        }
+    }
+
+    /// Rough token estimate (characters / 4), good enough for a soft cap
+    /// without depending on a per-provider tokenizer.
+    pub fn estimated_tokens(&self) -> usize {
+        self.messages.iter().map(|m| m.content.len() / 4).sum()
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &ChannelMessage> {
+        self.messages.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
@@ -0,0 +1,160 @@
+//! Tracks human-in-the-loop prompts (questionnaires, approvals) sent to a
+//! remote channel, and sweeps away ones nobody answered in time so the
+//! agent never hangs waiting on a user who went offline.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tokio::sync::oneshot;
+
+use crate::approval::ApprovalChoice;
+use crate::config::{DefaultInteractionAction, RemoteConfig};
+
+/// Default timeout used when a caller doesn't have a [`RemoteConfig`] on
+/// hand; `ChannelManager::sweep_expired` is normally driven by
+/// `RemoteConfig::interaction_timeout_secs` instead.
+pub const REMOTE_INTERACTION_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionKind {
+    Questionnaire,
+    Approval,
+}
+
+/// What a [`PendingInteraction`] resolves to, either from a real answer or
+/// from the timeout sweeper applying the configured default.
+#[derive(Debug, Clone)]
+pub enum InteractionOutcome {
+    QuestionnaireCancelled,
+    Approval(ApprovalChoice),
+}
+
+/// Emitted by the sweeper (and, in future, by real responses) so callers
+/// can react to interaction lifecycle changes without polling.
+#[derive(Debug, Clone)]
+pub enum RemoteEvent {
+    Timeout {
+        interaction_id: String,
+        channel_id: String,
+        kind: InteractionKind,
+    },
+    /// A turn finished and its cost is known, including for streaming
+    /// turns where usage only became available at stream end; see
+    /// [`crate::usage::apply_usage`].
+    Usage {
+        channel_id: String,
+        session_id: String,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+        /// `true` if the provider never reported usage and these counts
+        /// were estimated locally.
+        estimated: bool,
+    },
+}
+
+/// A prompt awaiting a human response on a remote channel. The `oneshot`
+/// sender is consumed either by the real response handler or by the
+/// timeout sweeper, whichever comes first.
+pub struct PendingInteraction {
+    pub id: String,
+    pub kind: InteractionKind,
+    pub channel_id: String,
+    created_at: Instant,
+    responder: Option<oneshot::Sender<InteractionOutcome>>,
+}
+
+impl PendingInteraction {
+    pub fn new(
+        id: impl Into<String>,
+        kind: InteractionKind,
+        channel_id: impl Into<String>,
+    ) -> (Self, oneshot::Receiver<InteractionOutcome>) {
+        let (tx, rx) = oneshot::channel();
+        (
+            Self {
+                id: id.into(),
+                kind,
+                channel_id: channel_id.into(),
+                created_at: Instant::now(),
+                responder: Some(tx),
+            },
+            rx,
+        )
+    }
+
+    fn is_expired(&self, now: Instant, timeout: Duration) -> bool {
+        now.duration_since(self.created_at) >= timeout
+    }
+
+    fn default_outcome(&self, config: &RemoteConfig) -> InteractionOutcome {
+        match self.kind {
+            InteractionKind::Questionnaire => InteractionOutcome::QuestionnaireCancelled,
+            InteractionKind::Approval => match config.default_approval_action {
+                DefaultInteractionAction::Deny | DefaultInteractionAction::Cancel => {
+                    InteractionOutcome::Approval(ApprovalChoice::Deny)
+                }
+            },
+        }
+    }
+}
+
+/// Tracks interactions currently awaiting a response across all channels.
+#[derive(Default)]
+pub struct ChannelManager {
+    pending: HashMap<String, PendingInteraction>,
+}
+
+impl ChannelManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, interaction: PendingInteraction) {
+        self.pending.insert(interaction.id.clone(), interaction);
+    }
+
+    /// Resolves a real (non-timeout) response, removing it from the
+    /// pending set. Returns `false` if no such interaction is pending
+    /// (already answered or already timed out).
+    pub fn resolve(&mut self, id: &str, outcome: InteractionOutcome) -> bool {
+        match self.pending.remove(id) {
+            Some(mut interaction) => {
+                if let Some(responder) = interaction.responder.take() {
+                    let _ = responder.send(outcome);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolves every interaction that has exceeded `config`'s timeout to
+    /// its configured default, returning a `Timeout` event for each one so
+    /// the caller can notify the originating channel.
+    pub fn sweep_expired(&mut self, now: Instant, config: &RemoteConfig) -> Vec<RemoteEvent> {
+        let timeout = Duration::from_secs(config.interaction_timeout_secs);
+        let expired_ids: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, interaction)| interaction.is_expired(now, timeout))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        let mut events = Vec::with_capacity(expired_ids.len());
+        for id in expired_ids {
+            if let Some(mut interaction) = self.pending.remove(&id) {
+                let outcome = interaction.default_outcome(config);
+                if let Some(responder) = interaction.responder.take() {
+                    let _ = responder.send(outcome);
+                }
+                events.push(RemoteEvent::Timeout {
+                    interaction_id: interaction.id,
+                    channel_id: interaction.channel_id,
+                    kind: interaction.kind,
+                });
+            }
+        }
+        events
+    }
+}
@@ -0,0 +1,89 @@
+//! Converts tark's Markdown replies to plain text for channels that can't
+//! render it (`ChannelInfo::supports_markdown == false`): strips emphasis
+//! markers and heading/list syntax, and unwraps fenced code blocks into
+//! indented plain lines instead of dropping the fence markers in place
+//! (which would otherwise read as stray backtick noise).
+
+use crate::channels::ChannelInfo;
+
+/// Returns `text` unchanged if `info` supports Markdown, otherwise its
+/// plaintext rendering via [`markdown_to_plaintext`].
+pub fn render_for_channel(text: &str, info: &ChannelInfo) -> String {
+    if info.supports_markdown {
+        text.to_string()
+    } else {
+        markdown_to_plaintext(text)
+    }
+}
+
+/// Converts `markdown` to plain text. This is a best-effort prose
+/// stripper, not a Markdown parser: it handles the constructs tark's own
+/// replies actually use (bold/italic/inline-code spans, headings,
+/// bulleted/numbered lists, fenced code blocks), not the full spec.
+pub fn markdown_to_plaintext(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_code_block = false;
+    let mut lines = markdown.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            out.push_str("    ");
+            out.push_str(line);
+        } else {
+            out.push_str(&flatten_line(line));
+        }
+        if lines.peek().is_some() {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn flatten_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("+ "))
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        return format!("{indent}- {}", strip_emphasis(rest));
+    }
+    if let Some(rest) = strip_ordered_marker(trimmed) {
+        return format!("{indent}- {}", strip_emphasis(rest));
+    }
+    if let Some(rest) = strip_heading_marker(trimmed) {
+        return strip_emphasis(rest);
+    }
+    strip_emphasis(line)
+}
+
+/// Strips a `"1. "`-style ordered list marker, returning the remainder.
+fn strip_ordered_marker(trimmed: &str) -> Option<&str> {
+    let digits_end = trimmed.find(|c: char| !c.is_ascii_digit())?;
+    if digits_end == 0 {
+        return None;
+    }
+    trimmed[digits_end..].strip_prefix(". ")
+}
+
+/// Strips a `"# "`/`"## "`/... heading marker, returning the remainder.
+fn strip_heading_marker(trimmed: &str) -> Option<&str> {
+    let hashes_end = trimmed.find(|c: char| c != '#')?;
+    if hashes_end == 0 {
+        return None;
+    }
+    trimmed[hashes_end..].strip_prefix(' ')
+}
+
+/// Drops `*`/`_` (bold/italic delimiters) and inline-code backticks
+/// entirely, rather than trying to pair them up — a plaintext channel has
+/// no way to set emphasized or inline-code text apart visually anyway, so
+/// there's nothing to preserve by keeping the delimiters balanced.
+fn strip_emphasis(line: &str) -> String {
+    line.chars().filter(|c| !matches!(c, '*' | '_' | '`')).collect()
+}
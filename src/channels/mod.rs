@@ -0,0 +1,36 @@
+//! Remote channel integrations (Slack, Discord, generic webhooks, ...).
+
+pub mod attachments;
+pub mod dedupe;
+pub mod gateway;
+pub mod header;
+pub mod history;
+pub mod interactions;
+pub mod markdown;
+pub mod rate_limit;
+pub mod send;
+pub mod shutdown;
+pub mod split;
+pub mod streaming;
+
+/// What a channel plugin can render and which attachment hosts it trusts,
+/// reported by the plugin so the sender/receiver don't need their own
+/// per-channel special cases; see
+/// [`crate::channels::markdown::render_for_channel`] and
+/// [`attachments::AttachmentAllowlist::with_extra_hosts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelInfo {
+    /// `false` for plain-text-only channels (SMS-style bridges, some
+    /// webhook targets) — outgoing text is converted to plaintext before
+    /// send rather than shown with raw Markdown syntax.
+    pub supports_markdown: bool,
+
+    /// Extra attachment host patterns (exact host or `*.suffix` glob) this
+    /// channel's own plugin is known to serve attachments from, on top of
+    /// [`attachments::AttachmentAllowlist::default_allowlist`] and any
+    /// deployment-wide `ChannelsConfig::extra_attachment_hosts`. A
+    /// self-hosted channel plugin backed by its own file store is the
+    /// typical case — the plugin is in the best position to know its own
+    /// host, not a global config file.
+    pub trusted_attachment_hosts: Vec<String>,
+}
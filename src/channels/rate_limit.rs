@@ -0,0 +1,97 @@
+//! Per-user rate limiting for remote channel requests, using a simple
+//! continuously refilling token bucket so a single chatty user can't starve a
+//! shared channel integration.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+pub struct UserRateLimiter {
+    max_tokens: f64,
+    refill_per_second: f64,
+    buckets: HashMap<String, Bucket>,
+}
+
+impl UserRateLimiter {
+    /// `max_tokens` requests allowed in a burst, refilling at
+    /// `refill_per_second` tokens/sec (e.g. `max_tokens=5,
+    /// refill_per_second=0.5` allows a burst of 5 then one every 2s).
+    pub fn new(max_tokens: f64, refill_per_second: f64) -> Self {
+        Self {
+            max_tokens,
+            refill_per_second,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Attempt to consume one token for `user_id`. Returns `false` (and
+    /// consumes nothing) if the user has no tokens left.
+    pub fn try_acquire(&mut self, user_id: &str) -> bool {
+        let max_tokens = self.max_tokens;
+        let refill_per_second = self.refill_per_second;
+        let bucket = self.buckets.entry(user_id.to_string()).or_insert_with(|| Bucket {
+            tokens: max_tokens,
+            last_refill: Instant::now(),
+        });
+
+        let elapsed = bucket.last_refill.elapsed().as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_second).min(max_tokens);
+        bucket.last_refill = Instant::now();
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop buckets for users who haven't made a request in `idle_for`, to
+    /// bound memory for channels with a large, mostly-inactive user base.
+    pub fn sweep_idle(&mut self, idle_for: Duration) {
+        self.buckets.retain(|_, bucket| bucket.last_refill.elapsed() < idle_for);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_a_burst_up_to_max_tokens_then_denies() {
+        let mut limiter = UserRateLimiter::new(3.0, 0.0);
+        assert!(limiter.try_acquire("alice"));
+        assert!(limiter.try_acquire("alice"));
+        assert!(limiter.try_acquire("alice"));
+        assert!(!limiter.try_acquire("alice"));
+    }
+
+    #[test]
+    fn tracks_buckets_independently_per_user() {
+        let mut limiter = UserRateLimiter::new(1.0, 0.0);
+        assert!(limiter.try_acquire("alice"));
+        assert!(!limiter.try_acquire("alice"));
+        assert!(limiter.try_acquire("bob"));
+    }
+
+    #[test]
+    fn sweep_idle_drops_buckets_older_than_the_threshold_but_keeps_fresh_ones() {
+        let mut limiter = UserRateLimiter::new(1.0, 0.0);
+        limiter.try_acquire("stale");
+        std::thread::sleep(Duration::from_millis(20));
+        limiter.try_acquire("fresh");
+
+        limiter.sweep_idle(Duration::from_millis(10));
+
+        // Both buckets are empty (max_tokens=1, already spent); after the
+        // sweep "stale" should have been evicted and get a fresh bucket
+        // (so it can acquire again), while "fresh" wasn't swept and stays
+        // exhausted.
+        assert!(limiter.try_acquire("stale"));
+        assert!(!limiter.try_acquire("fresh"));
+    }
+}
@@ -0,0 +1,40 @@
+//! Outbound message requests sent through channel plugins.
+
+/// Threading metadata attached to a send request so replies land in the
+/// right thread on channels that support it (Slack threads, Discord
+/// replies). Channels without threading ignore this.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadRef {
+    /// The message/thread ID this send is a reply to.
+    pub reply_to: Option<String>,
+    /// Whether the reply should also notify participants who aren't
+    /// already in the thread (Slack's `reply_broadcast`).
+    pub broadcast: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SendRequest {
+    pub channel_id: String,
+    pub text: String,
+    pub thread: ThreadRef,
+}
+
+impl SendRequest {
+    pub fn new(channel_id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            channel_id: channel_id.into(),
+            text: text.into(),
+            thread: ThreadRef::default(),
+        }
+    }
+
+    pub fn in_reply_to(mut self, message_id: impl Into<String>) -> Self {
+        self.thread.reply_to = Some(message_id.into());
+        self
+    }
+
+    pub fn broadcast(mut self, broadcast: bool) -> Self {
+        self.thread.broadcast = broadcast;
+        self
+    }
+}
@@ -0,0 +1,143 @@
+//! Coordinated shutdown for the channel poll loop: stop accepting new
+//! inbound messages, wait (with a timeout) for `process_inbound_message`
+//! tasks already running to finish and persist their sessions, and
+//! optionally persist whatever's left in the queue so it resumes on
+//! restart instead of being dropped.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainOutcome {
+    Completed,
+    TimedOut,
+}
+
+/// Tracks whether the poll loop should keep pulling new inbound messages
+/// off the queue, and how many tasks are currently processing one, so
+/// shutdown can wait for them to finish before the process exits.
+#[derive(Clone)]
+pub struct ShutdownCoordinator {
+    accepting: CancellationToken,
+    in_flight: Arc<AtomicUsize>,
+    idle: Arc<Notify>,
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self {
+            accepting: CancellationToken::new(),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            idle: Arc::new(Notify::new()),
+        }
+    }
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `true` while the poll loop should keep pulling new inbound
+    /// messages off the queue.
+    pub fn is_accepting(&self) -> bool {
+        !self.accepting.is_cancelled()
+    }
+
+    /// Stops accepting new inbound messages; tasks already started keep
+    /// running. Idempotent.
+    pub fn request_shutdown(&self) {
+        self.accepting.cancel();
+    }
+
+    /// Registers the start of a `process_inbound_message` task. The
+    /// returned guard decrements the in-flight count (and wakes any
+    /// waiting [`ShutdownCoordinator::drain`]) when dropped, regardless of
+    /// how the task ends.
+    pub fn track_task(&self) -> TaskGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        TaskGuard {
+            in_flight: self.in_flight.clone(),
+            idle: self.idle.clone(),
+        }
+    }
+
+    /// Waits for every tracked task to finish, up to `timeout`. Callers
+    /// should call [`ShutdownCoordinator::request_shutdown`] first so no
+    /// new tasks start while waiting.
+    pub async fn drain(&self, timeout: Duration) -> DrainOutcome {
+        let wait = async {
+            loop {
+                // `notified()` must be created before the condition check:
+                // a `track_task` guard dropping between the check and the
+                // await would otherwise be missed, hanging this forever.
+                let notified = self.idle.notified();
+                if self.in_flight.load(Ordering::SeqCst) == 0 {
+                    break;
+                }
+                notified.await;
+            }
+        };
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(()) => DrainOutcome::Completed,
+            Err(_) => DrainOutcome::TimedOut,
+        }
+    }
+}
+
+/// Held for the lifetime of one `process_inbound_message` task; see
+/// [`ShutdownCoordinator::track_task`].
+pub struct TaskGuard {
+    in_flight: Arc<AtomicUsize>,
+    idle: Arc<Notify>,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.idle.notify_waiters();
+    }
+}
+
+/// Persists whatever's left in the inbound queue to
+/// `<tark_dir>/channels/queue.jsonl` so it can be resumed on the next
+/// start instead of silently dropping messages that arrived right before
+/// shutdown.
+pub fn persist_queue(tark_dir: &Path, remaining: &[serde_json::Value]) -> std::io::Result<()> {
+    let dir = tark_dir.join("channels");
+    std::fs::create_dir_all(&dir)?;
+    let contents: String = remaining.iter().map(|v| format!("{v}\n")).collect();
+    std::fs::write(dir.join("queue.jsonl"), contents)
+}
+
+/// Loads a queue previously saved by [`persist_queue`], or an empty queue
+/// if none was saved.
+pub fn load_persisted_queue(tark_dir: &Path) -> std::io::Result<Vec<serde_json::Value>> {
+    let path = tark_dir.join("channels").join("queue.jsonl");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Installs a SIGTERM handler for `tark serve`/`start`: on signal, stops
+/// accepting new inbound messages and waits up to `drain_timeout` for
+/// in-flight tasks to finish before returning.
+#[cfg(unix)]
+pub async fn shutdown_on_sigterm(coordinator: ShutdownCoordinator, drain_timeout: Duration) -> DrainOutcome {
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .expect("failed to install SIGTERM handler");
+    sigterm.recv().await;
+    coordinator.request_shutdown();
+    coordinator.drain(drain_timeout).await
+}
@@ -0,0 +1,81 @@
+//! Splits long outgoing text into chunks no longer than a channel's max
+//! message length. Aware of Markdown code fences: breaks at the line
+//! boundary where the next line would overflow, whether or not that falls
+//! inside a fenced code block (a boundary outside a block is not
+//! preferred), and when a block spans a chunk boundary, closes it at the
+//! end of one chunk and reopens
+//! it (with the same language tag) at the start of the next, so syntax
+//! highlighting survives the split.
+
+/// Splits `text` into chunks of at most `max_len` characters each. `0`
+/// means no limit — the whole text comes back as a single chunk.
+pub fn split_message_by_chars(text: &str, max_len: usize) -> Vec<String> {
+    if max_len == 0 || text.chars().count() <= max_len {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut in_code_block = false;
+    let mut fence_lang = String::new();
+
+    for line in text.split_inclusive('\n') {
+        let is_fence_line = line.trim_start().trim_end_matches('\n').starts_with("```");
+
+        if !current.is_empty() && current.chars().count() + line.chars().count() > max_len {
+            flush(&mut chunks, &mut current, in_code_block, &fence_lang);
+        }
+
+        if line.chars().count() > max_len {
+            for piece in hard_split(line, max_len) {
+                if !current.is_empty() {
+                    flush(&mut chunks, &mut current, in_code_block, &fence_lang);
+                }
+                current.push_str(&piece);
+            }
+        } else {
+            current.push_str(line);
+        }
+
+        if is_fence_line {
+            if in_code_block {
+                in_code_block = false;
+                fence_lang.clear();
+            } else {
+                in_code_block = true;
+                fence_lang = line.trim_start().trim_end_matches('\n').trim_start_matches("```").to_string();
+            }
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Pushes `current` onto `chunks`, closing an open code fence first so the
+/// chunk is independently valid Markdown, then reopens the same fence (if
+/// it was open) at the start of the now-empty `current` for the next
+/// chunk to continue into.
+fn flush(chunks: &mut Vec<String>, current: &mut String, in_code_block: bool, fence_lang: &str) {
+    if in_code_block {
+        if !current.ends_with('\n') {
+            current.push('\n');
+        }
+        current.push_str("```\n");
+    }
+    chunks.push(std::mem::take(current));
+    if in_code_block {
+        current.push_str("```");
+        current.push_str(fence_lang);
+        current.push('\n');
+    }
+}
+
+/// Splits a single line longer than `max_len` into char-count-bounded
+/// pieces, for the rare case of one pathologically long line (e.g. a
+/// minified code line) that line-boundary splitting alone can't shrink.
+fn hard_split(line: &str, max_len: usize) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    chars.chunks(max_len).map(|chunk| chunk.iter().collect()).collect()
+}
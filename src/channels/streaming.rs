@@ -0,0 +1,181 @@
+//! Streaming response delivery for channels whose APIs support editing a
+//! message in place (Slack, Discord) rather than sending a new one per
+//! chunk.
+
+use tokio_util::sync::CancellationToken;
+
+use crate::config::RemoteConfig;
+
+/// Coalesces rapid `TextDelta` chunks so fewer edits reach the channel
+/// API: [`Self::push`] hands back the buffer as soon as at least
+/// `min_chars` of new text has accumulated, and the streaming loops below
+/// also flush whatever is buffered, however short, on every `debounce`
+/// tick.
+pub struct StreamCoalescer {
+    buffer: String,
+    min_chars: usize,
+}
+
+impl StreamCoalescer {
+    pub fn new(min_chars: usize) -> Self {
+        Self {
+            buffer: String::new(),
+            min_chars,
+        }
+    }
+
+    /// Append a delta and return the text to flush, if the buffer has
+    /// grown past `min_chars` since the last flush.
+    pub fn push(&mut self, delta: &str) -> Option<String> {
+        self.buffer.push_str(delta);
+        if self.buffer.chars().count() >= self.min_chars {
+            Some(std::mem::take(&mut self.buffer))
+        } else {
+            None
+        }
+    }
+
+    /// Flush whatever remains, e.g. once the stream ends.
+    pub fn flush(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.buffer))
+        }
+    }
+}
+
+/// Whether a channel can edit a previously sent message. Channels without
+/// this capability (plain webhooks, SMS-style bridges) fall back to
+/// sending a new message per flush instead of editing one in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditCapability {
+    CanEdit,
+    SendOnly,
+}
+
+/// Delivery action produced by the streaming loop; the caller maps this to
+/// the channel's actual API calls.
+pub enum DeliveryAction<'a> {
+    Edit { text: &'a str },
+    Send { text: &'a str },
+}
+
+/// Like [`respond_streaming`], but dispatches through `deliver` so callers
+/// on channels without edit support can send a fresh message per flush
+/// instead of editing the previous one.
+pub async fn respond_streaming_with_capability(
+    mut chunks: impl futures::Stream<Item = String> + Unpin,
+    cancellation: CancellationToken,
+    capability: EditCapability,
+    mut deliver: impl FnMut(DeliveryAction<'_>),
+    debounce: std::time::Duration,
+    min_chars: usize,
+) -> anyhow::Result<bool> {
+    use futures::StreamExt;
+
+    let mut coalescer = StreamCoalescer::new(min_chars);
+    let mut interrupted = false;
+    let mut ticker = tokio::time::interval(debounce);
+
+    let flush = |text: &str, deliver: &mut dyn FnMut(DeliveryAction<'_>)| match capability {
+        EditCapability::CanEdit => deliver(DeliveryAction::Edit { text }),
+        EditCapability::SendOnly => deliver(DeliveryAction::Send { text }),
+    };
+
+    loop {
+        tokio::select! {
+            _ = cancellation.cancelled() => {
+                interrupted = true;
+                break;
+            }
+            _ = ticker.tick() => {
+                if let Some(text) = coalescer.flush() {
+                    flush(&text, &mut deliver);
+                }
+            }
+            chunk = chunks.next() => {
+                match chunk {
+                    Some(delta) => {
+                        if let Some(text) = coalescer.push(&delta) {
+                            flush(&text, &mut deliver);
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if let Some(text) = coalescer.flush() {
+        flush(&text, &mut deliver);
+    }
+    Ok(interrupted)
+}
+
+/// Runs the channel's `respond_streaming` loop. If `cancellation` is
+/// triggered (e.g. the user sends a stop command) before the stream ends,
+/// it stops reading `chunks`, flushes what was buffered, and returns
+/// `true`; it sends no separate "interrupted" message of its own.
+pub async fn respond_streaming(
+    mut chunks: impl futures::Stream<Item = String> + Unpin,
+    cancellation: CancellationToken,
+    mut send_edit: impl FnMut(&str),
+    debounce: std::time::Duration,
+    min_chars: usize,
+) -> anyhow::Result<bool> {
+    use futures::StreamExt;
+
+    let mut coalescer = StreamCoalescer::new(min_chars);
+    let mut interrupted = false;
+    let mut ticker = tokio::time::interval(debounce);
+
+    loop {
+        tokio::select! {
+            _ = cancellation.cancelled() => {
+                interrupted = true;
+                break;
+            }
+            _ = ticker.tick() => {
+                if let Some(text) = coalescer.flush() {
+                    send_edit(&text);
+                }
+            }
+            chunk = chunks.next() => {
+                match chunk {
+                    Some(delta) => {
+                        if let Some(text) = coalescer.push(&delta) {
+                            send_edit(&text);
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if let Some(text) = coalescer.flush() {
+        send_edit(&text);
+    }
+    Ok(interrupted)
+}
+
+/// Like [`respond_streaming`], but takes its debounce/min-chars tuning
+/// from `config.remote.stream_debounce_ms`/`stream_min_chars` instead of
+/// the caller supplying them directly, so a deployment can tune a
+/// chattier or stricter channel without a code change.
+pub async fn respond_streaming_with_config(
+    chunks: impl futures::Stream<Item = String> + Unpin,
+    cancellation: CancellationToken,
+    send_edit: impl FnMut(&str),
+    config: &RemoteConfig,
+) -> anyhow::Result<bool> {
+    respond_streaming(
+        chunks,
+        cancellation,
+        send_edit,
+        std::time::Duration::from_millis(config.stream_debounce_ms),
+        config.stream_min_chars,
+    )
+    .await
+}
@@ -0,0 +1,50 @@
+//! `tark audit`: print recent entries from the approval audit log.
+
+use clap::Args;
+
+use crate::approval::audit::AuditEntry;
+use crate::core::timezone::{format_timestamp, TimezoneChoice};
+
+#[derive(Args, Debug)]
+pub struct AuditArgs {
+    /// Number of most recent entries to print.
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Renders `entries` for display, applying `zone` (from
+/// `config.display.timezone`) to each entry's stored-UTC timestamp; the
+/// stored `AuditEntry::timestamp` itself is untouched. `--json` output
+/// keeps the raw stored timestamp, since a machine consumer should get the
+/// unambiguous UTC value rather than a display-only rendering.
+pub fn render(entries: &[AuditEntry], json: bool, zone: &TimezoneChoice) -> String {
+    if json {
+        return serde_json::to_string_pretty(entries).unwrap_or_else(|_| "[]".to_string());
+    }
+    if entries.is_empty() {
+        return "(no audit entries)".to_string();
+    }
+    entries
+        .iter()
+        .map(|e| {
+            let who = e.user_id.as_deref().unwrap_or(&e.session_id);
+            let timestamp = render_timestamp(&e.timestamp, zone);
+            format!("{timestamp} {who} {:?} {} `{}`", e.choice, e.tool, e.command)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses `stored` (expected to be RFC 3339 UTC, the format
+/// `approval::audit::record` is given) and re-renders it in `zone`,
+/// falling back to the raw stored string if it isn't parseable — a
+/// display nicety shouldn't make an otherwise-readable audit log opaque.
+fn render_timestamp(stored: &str, zone: &TimezoneChoice) -> String {
+    match chrono::DateTime::parse_from_rfc3339(stored) {
+        Ok(parsed) => format_timestamp(parsed.with_timezone(&chrono::Utc), zone),
+        Err(_) => stored.to_string(),
+    }
+}
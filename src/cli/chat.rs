@@ -0,0 +1,164 @@
+//! `tark chat`: interactive CLI transport, including resume and scripted
+//! (JSON/NDJSON) output modes.
+//!
+//! System prompt precedence, for callers assembling a
+//! [`crate::prompt::pipeline::PromptPipeline`] for the session: the
+//! `--system`/`--system-file` override (if any) leads the assembled
+//! prompt, the agent/profile's own sections follow at their normal order,
+//! and `--append-rule` sections trail at the end in the order given on
+//! the command line. See [`ChatArgs::cli_prompt_sections`].
+
+use clap::Args;
+
+use crate::prompt::pipeline::PromptSection;
+use crate::session::storage::TarkStorage;
+
+#[derive(Args, Debug)]
+pub struct ChatArgs {
+    /// Inline system prompt, overriding the configured default.
+    #[arg(short = 's', long = "system")]
+    pub system: Option<String>,
+
+    /// Read the system prompt from a file instead of passing it inline.
+    #[arg(long = "system-file", conflicts_with = "system")]
+    pub system_file: Option<String>,
+
+    /// Append a named stored rule (see `TarkStorage::load_rule`) to the
+    /// assembled system prompt. Repeatable; rules are appended in the
+    /// order given.
+    #[arg(long = "append-rule")]
+    pub append_rule: Vec<String>,
+
+    /// Continue the most recently active conversation.
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Continue a specific named conversation instead of the most recent one.
+    #[arg(long, conflicts_with = "resume")]
+    pub resume_session: Option<String>,
+
+    /// Emit each turn as an NDJSON event on stdout instead of rendering a
+    /// TUI, for use in scripts and pipelines.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Replay a recorded `raw_log` transcript instead of calling a live
+    /// provider, for reproducing a reported bug offline.
+    #[arg(long)]
+    pub replay: Option<String>,
+
+    /// Simulate mutating/destructive tool calls instead of running them,
+    /// printing what the agent would have done.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Stop the agent loop after this many total tool calls.
+    #[arg(long)]
+    pub max_tool_calls: Option<usize>,
+
+    /// Stop the agent loop after this many seconds of wall-clock time.
+    #[arg(long)]
+    pub max_duration_secs: Option<u64>,
+
+    /// Maximum number of independent read-only tool calls run
+    /// concurrently within a single turn.
+    #[arg(long)]
+    pub tool_concurrency: Option<usize>,
+}
+
+impl ChatArgs {
+    /// Order assigned to the `--system`/`--system-file` override so it
+    /// leads every other section once sorted into the assembled prompt.
+    const CLI_OVERRIDE_ORDER: i32 = i32::MIN + 1;
+    /// Base order for `--append-rule` sections, placing them after
+    /// whatever order the agent/profile's own sections use.
+    const APPEND_RULE_BASE_ORDER: i32 = i32::MAX - 1000;
+
+    /// Resolve the effective system prompt, preferring the inline flag over
+    /// the file flag.
+    pub fn resolve_system_prompt(&self) -> anyhow::Result<Option<String>> {
+        if let Some(inline) = &self.system {
+            return Ok(Some(inline.clone()));
+        }
+        if let Some(path) = &self.system_file {
+            return Ok(Some(std::fs::read_to_string(path)?));
+        }
+        Ok(None)
+    }
+
+    /// Builds the [`PromptSection`]s contributed by this invocation's
+    /// `--system`/`--system-file` and `--append-rule` flags, for the
+    /// caller to add to the session's `PromptPipeline` alongside its
+    /// normal agent/profile stages — see the precedence note at the top
+    /// of this module.
+    pub fn cli_prompt_sections(&self, storage: &TarkStorage) -> anyhow::Result<Vec<PromptSection>> {
+        let mut sections = Vec::new();
+        if let Some(text) = self.resolve_system_prompt()? {
+            sections.push(PromptSection {
+                name: "cli-system-override".to_string(),
+                order: Self::CLI_OVERRIDE_ORDER,
+                content: text,
+            });
+        }
+        for (i, name) in self.append_rule.iter().enumerate() {
+            let content = storage
+                .load_rule(name)?
+                .ok_or_else(|| anyhow::anyhow!("no rule named `{name}` found"))?;
+            sections.push(PromptSection {
+                name: format!("rule:{name}"),
+                order: Self::APPEND_RULE_BASE_ORDER + i as i32,
+                content,
+            });
+        }
+        Ok(sections)
+    }
+
+    /// Which conversation to resume, if any was requested.
+    pub fn resume_target(&self) -> ResumeTarget {
+        if let Some(name) = &self.resume_session {
+            ResumeTarget::Named(name.clone())
+        } else if self.resume {
+            ResumeTarget::MostRecent
+        } else {
+            ResumeTarget::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeTarget {
+    None,
+    MostRecent,
+    Named(String),
+}
+
+/// One line of NDJSON output for `--json` mode.
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatEvent {
+    TextDelta { text: String },
+    ToolCall { name: String, args: serde_json::Value },
+    ToolResult { name: String, result: serde_json::Value },
+    Interrupted,
+    Done,
+    Error { message: String },
+}
+
+/// Writes one JSON object per line to a sink (stdout in practice),
+/// flushing after each event so consumers piping `tark chat --json` into
+/// another process see events as they happen rather than buffered.
+pub struct NdjsonWriter<W: std::io::Write> {
+    out: W,
+}
+
+impl<W: std::io::Write> NdjsonWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    pub fn write(&mut self, event: &ChatEvent) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.out, event)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()
+    }
+}
@@ -0,0 +1,70 @@
+//! `tark doctor`: check every configured provider's credentials and
+//! reachability up front, before a session starts.
+
+use clap::Args;
+
+use crate::llm::providers::health::ProviderCheckResult;
+
+#[derive(Args, Debug)]
+pub struct DoctorArgs {
+    /// Name of the provider to treat as default; its failure makes
+    /// `tark doctor` exit non-zero even if other providers pass.
+    #[arg(long)]
+    pub default_provider: Option<String>,
+
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Renders a pass/fail table. The exit-code decision is made separately
+/// by [`should_fail`].
+pub fn render(results: &[ProviderCheckResult], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(
+            &results
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "provider": r.provider,
+                        "passed": r.passed(),
+                        "auth_ok": r.auth_ok,
+                        "auth_detail": r.auth_detail,
+                        "ping_latency_ms": r.ping_latency.map(|d| d.as_millis()),
+                        "model_available": r.model_available,
+                        "error": r.error,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap_or_else(|_| "[]".to_string());
+    }
+
+    results
+        .iter()
+        .map(|r| {
+            let status = if r.passed() { "PASS" } else { "FAIL" };
+            let latency = r
+                .ping_latency
+                .map(|d| format!("{}ms", d.as_millis()))
+                .unwrap_or_else(|| "-".to_string());
+            let detail = r
+                .error
+                .as_deref()
+                .or(r.auth_detail.as_deref())
+                .unwrap_or("-");
+            format!("{status}  {:<20} latency={latency:<8} {detail}", r.provider)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `true` if the process should exit non-zero: the default provider (when
+/// specified) failed its check.
+pub fn should_fail(results: &[ProviderCheckResult], default_provider: Option<&str>) -> bool {
+    match default_provider {
+        Some(name) => results
+            .iter()
+            .any(|r| r.provider == name && !r.passed()),
+        None => false,
+    }
+}
@@ -0,0 +1,161 @@
+//! `tark explain`: read-only repo orientation for new users — walks the
+//! project, samples key files, and asks the model to produce a concise
+//! architecture summary with a file map, saved to `.tark/OVERVIEW.md`.
+//!
+//! This module owns the walk/sample/cache logic; actually prompting a
+//! provider with the sampled content is left to the caller, same as
+//! `cli::review` leaves the `review_code` tool call to its caller.
+
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use clap::Args;
+
+use crate::tools::ignore_rules::IgnoreRules;
+
+#[derive(Args, Debug)]
+pub struct ExplainArgs {
+    /// Directory to explain. Defaults to the current directory.
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Regenerate the overview even if a cached one matches the current
+    /// repo content hash.
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+/// Caps how many sampled files go into the prompt, so a huge repo doesn't
+/// blow the context window or the read budget below.
+const MAX_SAMPLED_FILES: usize = 40;
+/// Caps total bytes read across all sampled files.
+const MAX_SAMPLED_BYTES: usize = 200_000;
+/// Per-file cap, so one huge generated file doesn't eat the whole budget.
+const MAX_FILE_BYTES: usize = 20_000;
+
+/// Directory names never descended into while sampling, on top of whatever
+/// `.tarkignore`/`config.workspace.ignore_patterns` adds via [`IgnoreRules`]
+/// — these are noise in every repo, so they're skipped even with no
+/// ignore file at all.
+const IGNORED_DIRS: &[&str] = &[".git", ".tark", "target", "node_modules", "dist", "build", "vendor", ".venv"];
+
+/// File names sampled first, before every other file in walk order, since
+/// they tend to carry the most orientation value per byte.
+const PRIORITY_FILE_NAMES: &[&str] = &[
+    "README.md", "README", "Cargo.toml", "package.json", "pyproject.toml", "go.mod", "lib.rs", "main.rs",
+];
+
+#[derive(Debug, Clone)]
+pub struct SampledFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// Walks `root` (skipping [`IGNORED_DIRS`] and anything `ignore` excludes)
+/// and reads up to [`MAX_SAMPLED_FILES`] files, prioritizing
+/// [`PRIORITY_FILE_NAMES`], bounded by [`MAX_SAMPLED_BYTES`] total.
+pub fn sample_files(root: &Path, ignore: &IgnoreRules) -> anyhow::Result<Vec<SampledFile>> {
+    let mut candidates = Vec::new();
+    for entry in walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| {
+            let is_dir = e.file_type().is_dir();
+            if is_dir && is_ignored_dir(e.file_name().to_str().unwrap_or("")) {
+                return false;
+            }
+            !ignore.is_ignored(e.path(), is_dir)
+        })
+        .filter_map(Result::ok)
+    {
+        if entry.file_type().is_file() {
+            candidates.push(entry.path().to_path_buf());
+        }
+    }
+
+    // Priority files first, in declared order; everything else after, in
+    // walk order (depth-first, as `walkdir` yields entries).
+    candidates.sort_by_key(|path| {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        PRIORITY_FILE_NAMES.iter().position(|p| *p == name).unwrap_or(PRIORITY_FILE_NAMES.len())
+    });
+
+    let mut sampled = Vec::new();
+    let mut total_bytes = 0usize;
+    for path in candidates {
+        if sampled.len() >= MAX_SAMPLED_FILES || total_bytes >= MAX_SAMPLED_BYTES {
+            break;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let truncated: String = content.chars().take(MAX_FILE_BYTES).collect();
+        total_bytes += truncated.len();
+        let relative = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+        sampled.push(SampledFile { path: relative, content: truncated });
+    }
+    Ok(sampled)
+}
+
+fn is_ignored_dir(name: &str) -> bool {
+    IGNORED_DIRS.contains(&name)
+}
+
+/// Hashes the sampled files' paths and content so regeneration can be
+/// skipped when nothing relevant has changed since the last run.
+pub fn content_hash(files: &[SampledFile]) -> String {
+    let mut sorted: Vec<&SampledFile> = files.iter().collect();
+    sorted.sort_by(|a, b| a.path.cmp(&b.path));
+    let mut hasher = Sha256::new();
+    for file in sorted {
+        hasher.update(file.path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(file.content.as_bytes());
+        hasher.update(b"\0");
+    }
+    hex_encode(&hasher.finalize())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Marker line appended to a generated overview so a later run can tell
+/// whether the repo has changed without a separate cache file.
+fn hash_marker(hash: &str) -> String {
+    format!("<!-- tark:content-hash:{hash} -->")
+}
+
+/// Extracts the content hash embedded by [`hash_marker`] in a previously
+/// generated overview, if present.
+pub fn cached_hash(existing_markdown: &str) -> Option<&str> {
+    existing_markdown.lines().find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("<!-- tark:content-hash:")?.strip_suffix(" -->")
+    })
+}
+
+/// `true` if the overview should be (re)generated: no cached overview
+/// exists, its embedded hash doesn't match the current content hash, or
+/// `--refresh` was passed.
+pub fn needs_regeneration(existing_markdown: Option<&str>, current_hash: &str, refresh: bool) -> bool {
+    if refresh {
+        return true;
+    }
+    match existing_markdown.and_then(cached_hash) {
+        Some(cached) => cached != current_hash,
+        None => true,
+    }
+}
+
+/// Renders the saved `.tark/OVERVIEW.md`: the model's summary, a file map
+/// of what was sampled, and the trailing hash marker used by
+/// [`needs_regeneration`] on the next run.
+pub fn render_overview(summary: &str, files: &[SampledFile], hash: &str) -> String {
+    let mut file_map: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    file_map.sort_unstable();
+    let map_section = file_map.iter().map(|p| format!("- {p}")).collect::<Vec<_>>().join("\n");
+    format!("{summary}\n\n## File map\n\n{map_section}\n\n{}\n", hash_marker(hash))
+}
+
+pub fn overview_path(root: &Path) -> PathBuf {
+    root.join(".tark").join("OVERVIEW.md")
+}
@@ -0,0 +1,11 @@
+//! `tark gateway <plugin_id>`: opens and maintains the persistent gateway
+//! connection for a channel plugin that needs one (Discord and similar),
+//! alongside the poll and webhook paths used by other channels.
+
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct GatewayArgs {
+    /// Id of the channel plugin to open a gateway connection for.
+    pub plugin_id: String,
+}
@@ -0,0 +1,37 @@
+//! `tark mcp`: list, test, and introspect configured MCP servers.
+
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug)]
+pub struct McpArgs {
+    #[command(subcommand)]
+    pub command: McpCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum McpCommand {
+    /// List configured MCP servers and whether they're currently reachable.
+    List,
+    /// Connect to a server and print the tools it advertises.
+    Tools { server: String },
+    /// Connect to a server and report success/failure without listing tools.
+    Test { server: String },
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct McpServerStatus {
+    pub name: String,
+    pub reachable: bool,
+    pub error: Option<String>,
+}
+
+pub fn render_list(statuses: &[McpServerStatus]) -> String {
+    statuses
+        .iter()
+        .map(|s| match &s.error {
+            Some(err) => format!("{}: unreachable ({err})", s.name),
+            None => format!("{}: ok", s.name),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,52 @@
+//! CLI entry points: argument parsing and subcommand dispatch.
+
+use clap::{Parser, Subcommand};
+
+pub mod audit;
+pub mod chat;
+pub mod doctor;
+pub mod explain;
+pub mod gateway;
+pub mod mcp;
+pub mod models;
+pub mod plan;
+pub mod review;
+pub mod secure_store;
+pub mod session;
+pub mod usage;
+
+#[derive(Parser, Debug)]
+#[command(name = "tark", version, about = "AI-powered CLI agent")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Start the interactive TUI chat.
+    Chat(chat::ChatArgs),
+    /// Run a one-shot, non-interactive code review and print the results.
+    Review(review::ReviewArgs),
+    /// Manage configured MCP servers.
+    Mcp(mcp::McpArgs),
+    /// Print a cost/usage summary.
+    Usage(usage::UsageArgs),
+    /// Encrypt/decrypt stored credential files.
+    SecureStore(secure_store::SecureStoreArgs),
+    /// Print recent approval audit log entries.
+    Audit(audit::AuditArgs),
+    /// Generate and persist a step-by-step plan without executing it.
+    Plan(plan::PlanArgs),
+    /// Inspect or refresh the model capability database.
+    Models(models::ModelsArgs),
+    /// Check every configured provider's credentials and reachability.
+    Doctor(doctor::DoctorArgs),
+    /// Run the persistent gateway connection for a channel plugin that
+    /// needs one (Discord and similar), alongside the poll/webhook paths.
+    Gateway(gateway::GatewayArgs),
+    /// Move sessions between machines by exporting/importing a bundle.
+    Session(session::SessionArgs),
+    /// Generate a read-only architecture overview for new users.
+    Explain(explain::ExplainArgs),
+}
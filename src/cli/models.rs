@@ -0,0 +1,40 @@
+//! `tark models`: inspect and refresh the model capability database.
+
+use clap::{Args, Subcommand};
+
+use crate::llm::models_db::ModelsDb;
+
+#[derive(Args, Debug)]
+pub struct ModelsArgs {
+    #[command(subcommand)]
+    pub command: ModelsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ModelsCommand {
+    /// Print known models and their capabilities.
+    List,
+    /// Fetch the latest model database from models.dev and cache it to
+    /// `~/.config/tark/models.json`.
+    Refresh,
+}
+
+pub fn render_list(db: &ModelsDb) -> String {
+    let mut entries: Vec<_> = db.entries().collect();
+    entries.sort_by(|a, b| (&a.provider, &a.id).cmp(&(&b.provider, &b.id)));
+    entries
+        .iter()
+        .map(|e| {
+            format!(
+                "{}/{}  vision={} tools={} thinking={} max_context={}",
+                e.provider,
+                e.id,
+                e.capabilities.vision,
+                e.capabilities.tools,
+                e.capabilities.thinking,
+                e.capabilities.max_context_tokens
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
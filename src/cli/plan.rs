@@ -0,0 +1,28 @@
+//! `tark plan`: generate a step-by-step plan without executing it, and
+//! `tark plan run` to execute a previously saved one with checkpoints.
+
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug)]
+pub struct PlanArgs {
+    #[command(subcommand)]
+    pub command: Option<PlanCommand>,
+
+    /// The goal to produce a plan for. Ignored when a subcommand is given.
+    pub goal: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlanCommand {
+    /// Execute a saved plan step by step, pausing for approval between
+    /// steps and checkpointing progress back to the plan file.
+    Run {
+        /// Name (slug) of the saved plan, as printed by `tark plan`.
+        name: String,
+
+        /// Resume from this step index (0-based) instead of the first
+        /// non-done step.
+        #[arg(long)]
+        from_step: Option<usize>,
+    },
+}
@@ -0,0 +1,39 @@
+//! `tark review`: run the `review_code` tool once against a path and print
+//! the findings, without starting a full chat session.
+
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct ReviewArgs {
+    /// File or directory to review. Defaults to the current directory.
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Emit findings as JSON instead of human-readable text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct ReviewFindingOutput {
+    pub path: String,
+    pub line: u32,
+    pub severity: String,
+    pub message: String,
+}
+
+/// Render findings for `tark review`. Kept separate from the tool call
+/// itself so it can be unit-exercised without a live provider.
+pub fn render(findings: &[ReviewFindingOutput], json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(findings).unwrap_or_else(|_| "[]".to_string());
+    }
+    if findings.is_empty() {
+        return "No findings.".to_string();
+    }
+    findings
+        .iter()
+        .map(|f| format!("{}:{} [{}] {}", f.path, f.line, f.severity, f.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,66 @@
+//! `tark secure-store`: encrypt existing plaintext credential files.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::security::secure_store::{
+    derive_key_from_env, encrypt_file_in_place, generate_key, rotate_dir, SecureStore, SecureStoreError,
+};
+
+#[derive(Args, Debug)]
+pub struct SecureStoreArgs {
+    #[command(subcommand)]
+    pub command: SecureStoreCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SecureStoreCommand {
+    /// Encrypt a plaintext credential file, writing a `.enc` sibling.
+    Encrypt { path: String },
+    /// Decrypt a `.enc` file to stdout, for inspection/migration.
+    Decrypt { path: String },
+    /// Re-encrypt every stored credential under a newly generated key.
+    RotateKey {
+        #[arg(long, default_value = "~/.config/tark/credentials")]
+        dir: String,
+    },
+}
+
+/// Result of running a [`SecureStoreCommand`], for the embedding binary to
+/// render however it sees fit (print a path, dump plaintext to stdout,
+/// report the rotated files and the new key the caller must now persist).
+#[derive(Debug)]
+pub enum SecureStoreOutcome {
+    Encrypted { out_path: PathBuf },
+    Decrypted { plaintext: Vec<u8> },
+    RotatedKey { rotated_files: Vec<PathBuf>, new_key: [u8; 32] },
+}
+
+/// Runs `command` against the key derived from `TARK_MASTER_KEY` (see
+/// [`derive_key_from_env`]). Like every other `cli` module, this crate
+/// doesn't call it itself — `SecureStoreArgs` only describes the command
+/// line surface; an embedding binary wires it to `clap`'s dispatch and
+/// renders the returned [`SecureStoreOutcome`].
+pub fn execute(command: &SecureStoreCommand) -> Result<SecureStoreOutcome, SecureStoreError> {
+    let key = derive_key_from_env()?;
+    let store = SecureStore::new(&key);
+
+    match command {
+        SecureStoreCommand::Encrypt { path } => {
+            let out_path = encrypt_file_in_place(&store, std::path::Path::new(path))?;
+            Ok(SecureStoreOutcome::Encrypted { out_path })
+        }
+        SecureStoreCommand::Decrypt { path } => {
+            let envelope = std::fs::read(path)?;
+            let plaintext = store.decrypt(&envelope)?;
+            Ok(SecureStoreOutcome::Decrypted { plaintext })
+        }
+        SecureStoreCommand::RotateKey { dir } => {
+            let new_key = generate_key();
+            let new_store = SecureStore::new(&new_key);
+            let rotated_files = rotate_dir(&store, &new_store, std::path::Path::new(dir))?;
+            Ok(SecureStoreOutcome::RotatedKey { rotated_files, new_key })
+        }
+    }
+}
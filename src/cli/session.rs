@@ -0,0 +1,31 @@
+//! `tark session`: move sessions between machines via
+//! [`crate::session::export`].
+
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    pub command: SessionCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SessionCommand {
+    /// Package one or more sessions into a bundle file.
+    Export {
+        /// Names of the sessions to export.
+        ids: Vec<String>,
+        #[arg(long)]
+        out: String,
+        /// Encrypt the bundle's contents; requires the same master key to import.
+        #[arg(long)]
+        encrypt: bool,
+    },
+    /// Restore sessions from a bundle written by `tark session export`.
+    Import {
+        bundle: String,
+        /// Decrypt the bundle's contents; must match how it was exported.
+        #[arg(long)]
+        encrypt: bool,
+    },
+}
@@ -0,0 +1,35 @@
+//! `tark usage`: print a cost/token summary read from the `UsageTracker`
+//! log.
+
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct UsageArgs {
+    /// Restrict the summary to a single session.
+    #[arg(long)]
+    pub session: Option<String>,
+
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn render(summary: &crate::usage::UsageSummary, json: bool) -> String {
+    if json {
+        return serde_json::to_string_pretty(summary).unwrap_or_else(|_| "{}".to_string());
+    }
+    let mut lines = vec![
+        format!("requests: {}", summary.total_requests),
+        format!(
+            "tokens: {} in / {} out",
+            summary.total_input_tokens, summary.total_output_tokens
+        ),
+        format!("cost: ${:.4}", summary.total_cost_usd),
+    ];
+    for (model, usage) in &summary.by_model {
+        lines.push(format!(
+            "  {model}: {} req, {} in / {} out, ${:.4}",
+            usage.requests, usage.input_tokens, usage.output_tokens, usage.cost_usd
+        ));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,68 @@
+//! Local fill-in-the-middle (FIM) completion cache, keyed by the
+//! prefix/suffix context around the cursor so repeated requests at the
+//! same edit point (e.g. after an undo/redo) skip the model call.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FimCacheKey {
+    /// Trailing slice of the prefix used for the cache key — the full
+    /// prefix can be large, so only the last `CONTEXT_CHARS` matter for
+    /// matching.
+    pub prefix_tail: String,
+    pub suffix_head: String,
+}
+
+const CONTEXT_CHARS: usize = 256;
+
+impl FimCacheKey {
+    pub fn new(prefix: &str, suffix: &str) -> Self {
+        Self {
+            prefix_tail: tail(prefix, CONTEXT_CHARS),
+            suffix_head: head(suffix, CONTEXT_CHARS),
+        }
+    }
+}
+
+fn tail(s: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let start = chars.len().saturating_sub(max_chars);
+    chars[start..].iter().collect()
+}
+
+fn head(s: &str, max_chars: usize) -> String {
+    s.chars().take(max_chars).collect()
+}
+
+#[derive(Default)]
+pub struct FimCache {
+    entries: HashMap<FimCacheKey, String>,
+    max_entries: usize,
+    insertion_order: std::collections::VecDeque<FimCacheKey>,
+}
+
+impl FimCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_entries,
+            insertion_order: std::collections::VecDeque::new(),
+        }
+    }
+
+    pub fn get(&self, key: &FimCacheKey) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: FimCacheKey, completion: String) {
+        if !self.entries.contains_key(&key) {
+            self.insertion_order.push_back(key.clone());
+            if self.insertion_order.len() > self.max_entries {
+                if let Some(oldest) = self.insertion_order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        self.entries.insert(key, completion);
+    }
+}
@@ -0,0 +1,53 @@
+//! `complete_fim`: the fill-in-the-middle completion request shape sent
+//! to the provider.
+
+#[derive(Debug, Clone)]
+pub struct FimRequest {
+    pub prefix: String,
+    pub suffix: String,
+    /// Sequences that should stop generation early — typically the
+    /// start of the next line already present in `suffix`, so the model
+    /// doesn't regenerate code that's already there.
+    pub stop_sequences: Vec<String>,
+    pub max_tokens: u32,
+}
+
+impl FimRequest {
+    pub fn new(prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+            stop_sequences: Vec::new(),
+            max_tokens: 128,
+        }
+    }
+
+    pub fn with_stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.stop_sequences = stop_sequences;
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+}
+
+/// Truncate a raw completion at the first occurrence of any configured
+/// stop sequence, for providers that don't support native stop sequences
+/// and so return the full continuation regardless.
+pub fn truncate_at_stop_sequence(completion: &str, stop_sequences: &[String]) -> String {
+    let mut earliest: Option<usize> = None;
+    for stop in stop_sequences {
+        if stop.is_empty() {
+            continue;
+        }
+        if let Some(idx) = completion.find(stop.as_str()) {
+            earliest = Some(earliest.map_or(idx, |e: usize| e.min(idx)));
+        }
+    }
+    match earliest {
+        Some(idx) => completion[..idx].to_string(),
+        None => completion.to_string(),
+    }
+}
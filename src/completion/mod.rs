@@ -0,0 +1,23 @@
+//! Ghost-text / fill-in-the-middle completion support.
+
+pub mod cache;
+pub mod fim;
+pub mod template;
+
+/// Whether ghost text should suggest a single line or extend across
+/// multiple lines (e.g. completing a whole function body).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionMode {
+    SingleLine,
+    MultiLine,
+}
+
+/// Trims a raw model completion to the requested mode: single-line mode
+/// cuts at the first newline, multi-line mode passes the text through
+/// unchanged.
+pub fn apply_mode(mode: CompletionMode, completion: &str) -> String {
+    match mode {
+        CompletionMode::SingleLine => completion.split('\n').next().unwrap_or("").to_string(),
+        CompletionMode::MultiLine => completion.to_string(),
+    }
+}
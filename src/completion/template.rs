@@ -0,0 +1,53 @@
+//! Language-specific FIM prompt templating. Different providers (and
+//! different base models within a provider) expect the prefix/suffix
+//! wrapped in different sentinel tokens; this maps a language id to the
+//! right wrapper so the completion module doesn't need per-provider
+//! special-casing at the call site.
+
+use crate::completion::fim::FimRequest;
+
+#[derive(Debug, Clone)]
+pub struct FimTemplate {
+    pub prefix_token: &'static str,
+    pub suffix_token: &'static str,
+    pub middle_token: &'static str,
+    /// Comment syntax used to inject the language id as a hint when the
+    /// underlying model doesn't have a first-class FIM mode.
+    pub line_comment: &'static str,
+}
+
+const DEFAULT_TEMPLATE: FimTemplate = FimTemplate {
+    prefix_token: "<PRE>",
+    suffix_token: "<SUF>",
+    middle_token: "<MID>",
+    line_comment: "//",
+};
+
+pub fn template_for_language(language_id: &str) -> FimTemplate {
+    match language_id {
+        "python" => FimTemplate {
+            line_comment: "#",
+            ..DEFAULT_TEMPLATE
+        },
+        "html" | "xml" => FimTemplate {
+            line_comment: "<!--",
+            ..DEFAULT_TEMPLATE
+        },
+        _ => DEFAULT_TEMPLATE,
+    }
+}
+
+/// Render a FIM request into the provider-facing prompt string for a
+/// given language.
+pub fn render(request: &FimRequest, language_id: &str) -> String {
+    let template = template_for_language(language_id);
+    format!(
+        "{comment} language: {language_id}\n{pre}{prefix}{suf}{suffix}{mid}",
+        comment = template.line_comment,
+        pre = template.prefix_token,
+        prefix = request.prefix,
+        suf = template.suffix_token,
+        suffix = request.suffix,
+        mid = template.middle_token,
+    )
+}
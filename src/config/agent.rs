@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// Bounds on how the agent loop itself behaves, distinct from
+/// [`AgentToolsConfig`]'s tool-permission rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentConfig {
+    /// How many times the agent may ask the model to re-emit a tool call
+    /// whose arguments failed schema validation, before treating the call
+    /// as a hard failure. See `crate::tools::schema::validate` and
+    /// `ChatAgent::run`.
+    pub max_tool_arg_retries: usize,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self { max_tool_arg_retries: 2 }
+    }
+}
+
+/// How much the agent is trusted to act without a human reviewing each
+/// command: `Manual` requires approval for every mutating/destructive
+/// call, `Balanced` approves low-risk patterns automatically, and
+/// `Autonomous` runs without approval prompts at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustLevel {
+    #[default]
+    Manual,
+    Balanced,
+    Autonomous,
+}
+
+/// Controls which tools the agent is permitted to call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentToolsConfig {
+    /// If non-empty, only these tool names may be called; all others are
+    /// denied regardless of `denylist`.
+    pub allowlist: Vec<String>,
+    /// Tool names that may never be called, even if present in
+    /// `allowlist`.
+    pub denylist: Vec<String>,
+    pub trust_level: TrustLevel,
+    /// Extra environment variable names the `shell` tool's sanitized
+    /// environment should include, beyond the built-in safe defaults
+    /// (`PATH`, `HOME`, ...); see [`crate::tools::shell::sanitized_env`].
+    pub shell_env_allowlist: Vec<String>,
+    /// Glob patterns (see `crate::tools::search::glob_match`) matched
+    /// against a shell command's leading program name; a match is refused
+    /// outright, regardless of trust level or any remembered approval.
+    pub shell_blocked: Vec<String>,
+    /// Like `shell_blocked`, but a match always prompts for approval
+    /// instead of being refused — even at a trust level, or with a
+    /// remembered "always approve" pattern, that would otherwise skip the
+    /// prompt. See `crate::tools::shell::classify_shell_command`.
+    pub shell_always_ask: Vec<String>,
+    /// Glob patterns matched case-insensitively against a `web_fetch`
+    /// request's hostname (see `crate::tools::web_fetch::domain_allowed`);
+    /// a request to a host matching none of these is refused. Empty means nothing is allowed,
+    /// not everything — an explicit opt-in per deployment.
+    pub web_allowlist: Vec<String>,
+}
+
+impl AgentToolsConfig {
+    pub fn is_allowed(&self, tool_name: &str) -> bool {
+        if self.denylist.iter().any(|t| t == tool_name) {
+            return false;
+        }
+        self.allowlist.is_empty() || self.allowlist.iter().any(|t| t == tool_name)
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::channels::attachments::AttachmentAllowlist;
+use crate::channels::ChannelInfo;
+
+/// Configuration shared by all remote channel integrations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelsConfig {
+    /// Maximum number of messages retained per conversation. Once
+    /// exceeded, the oldest message is dropped to make room for the new
+    /// one.
+    pub max_history_messages: usize,
+
+    /// Extra host patterns (exact host or `*.suffix`) allowed for
+    /// attachment downloads, on top of the built-in defaults.
+    pub extra_attachment_hosts: Vec<String>,
+
+    /// Per-plugin shared secret used to verify `POST
+    /// /channels/:plugin_id/webhook` signatures, keyed by plugin id.
+    pub webhook_secrets: HashMap<String, String>,
+}
+
+impl Default for ChannelsConfig {
+    fn default() -> Self {
+        Self {
+            max_history_messages: 200,
+            extra_attachment_hosts: Vec::new(),
+            webhook_secrets: HashMap::new(),
+        }
+    }
+}
+
+impl ChannelsConfig {
+    /// The effective [`AttachmentAllowlist`] for a given channel: the
+    /// built-in defaults, plus this deployment's
+    /// [`Self::extra_attachment_hosts`], plus `channel`'s own
+    /// [`ChannelInfo::trusted_attachment_hosts`].
+    pub fn attachment_allowlist(&self, channel: &ChannelInfo) -> AttachmentAllowlist {
+        AttachmentAllowlist::with_extra_hosts(
+            self.extra_attachment_hosts
+                .iter()
+                .cloned()
+                .chain(channel.trusted_attachment_hosts.iter().cloned()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_allowlist_merges_config_and_channel_hosts() {
+        let config = ChannelsConfig {
+            extra_attachment_hosts: vec!["files.example.com".to_string()],
+            ..ChannelsConfig::default()
+        };
+        let channel = ChannelInfo {
+            supports_markdown: true,
+            trusted_attachment_hosts: vec!["*.plugin-storage.internal".to_string()],
+        };
+
+        let allowlist = config.attachment_allowlist(&channel);
+        assert!(allowlist.is_allowed("https://files.example.com/a.png"));
+        assert!(allowlist.is_allowed("https://cdn.plugin-storage.internal/a.png"));
+        assert!(allowlist.is_allowed("https://bucket.s3.amazonaws.com/a.png"));
+        assert!(!allowlist.is_allowed("https://evil.example/a.png"));
+    }
+}
@@ -0,0 +1,21 @@
+//! Settings governing how tark renders things for a human to read, as
+//! opposed to how it stores them — storage (session files, the audit log,
+//! ...) always stays UTC regardless of this config.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// An IANA timezone name (e.g. `"America/New_York"`), or `"local"` to
+    /// use the system's local timezone. Applied when formatting the
+    /// session header in channels and timestamps in CLI output; see
+    /// [`crate::core::timezone`]. An unrecognized name falls back to UTC.
+    pub timezone: String,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self { timezone: "UTC".to_string() }
+    }
+}
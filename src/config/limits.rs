@@ -0,0 +1,18 @@
+//! Spend guardrails softer than [`crate::usage::limits::SpendLimiter`]'s
+//! hard stop: a threshold that downshifts to a cheaper model instead of
+//! refusing to continue.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitsConfig {
+    /// Projected session spend, in USD, past which the agent should
+    /// downshift to `downshift_model` rather than stop outright. `None`
+    /// disables downshifting.
+    pub soft_limit_usd: Option<f64>,
+    /// Model to switch to once `soft_limit_usd` is crossed, as
+    /// `"{provider}/{model}"`. Must support the capabilities the session
+    /// needs (see [`crate::llm::model_selector`]).
+    pub downshift_model: Option<String>,
+}
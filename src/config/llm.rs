@@ -0,0 +1,41 @@
+//! Per-provider request-shaping knobs that don't belong in the generic
+//! model capability database (see [`crate::llm::models_db`]) — currently
+//! just OpenRouter's fallback/routing preferences.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    pub openrouter: OpenRouterConfig,
+    /// Overall timeout for a provider's non-streaming `chat` request.
+    /// `None` (the default, preserving prior behavior) means no timeout
+    /// beyond whatever the underlying client is otherwise configured
+    /// with. Distinct from a stream's idle timeout, which bounds the gap
+    /// between chunks rather than the whole request.
+    pub request_timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterConfig {
+    pub route: OpenRouterRouteConfig,
+}
+
+/// Mirrors OpenRouter's own request-body routing fields so they can be
+/// passed through largely unchanged; see
+/// <https://openrouter.ai/docs/features/model-routing>.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenRouterRouteConfig {
+    /// Fallback models tried in order if the primary model errors or is
+    /// unavailable, sent as OpenRouter's top-level `models` array.
+    pub models: Vec<String>,
+    /// Upstream provider preferences, passed through verbatim as
+    /// OpenRouter's `provider` object (e.g. `{"sort": "price"}` to prefer
+    /// the cheapest upstream, or `{"order": ["Together"]}` to require
+    /// one). Left as raw JSON since OpenRouter's routing schema is wider
+    /// than it's worth modelling here.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_preferences: Option<serde_json::Value>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the `tark` LSP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LspConfig {
+    /// Minimum self-rated confidence (0.0-1.0) a quickfix must meet to be
+    /// applied directly. Fixes below this threshold are surfaced as
+    /// preview-only actions that require explicit user confirmation.
+    pub quickfix_min_confidence: f32,
+
+    /// Whether to compute and publish inlay hints (inferred types, call-site
+    /// parameter names). Users on metered models may want to disable this
+    /// since it issues an LLM request per visible range.
+    pub inlay_hints: bool,
+
+    /// Minimum severity a diagnostic must have to be published.
+    pub diagnostics_min_severity: crate::lsp::diagnostics::Severity,
+
+    /// Maximum number of diagnostics published per file, keeping the most
+    /// severe ones when a file exceeds the cap.
+    pub diagnostics_max_per_file: usize,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        Self {
+            quickfix_min_confidence: 0.7,
+            inlay_hints: true,
+            diagnostics_min_severity: crate::lsp::diagnostics::Severity::Information,
+            diagnostics_max_per_file: 100,
+        }
+    }
+}
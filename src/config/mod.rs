@@ -0,0 +1,46 @@
+//! Shared configuration types loaded from `~/.config/tark/config.toml` (or
+//! the project-local `.tark/config.toml` override).
+
+use serde::{Deserialize, Serialize};
+
+mod agent;
+mod channels;
+mod display;
+mod limits;
+mod llm;
+mod lsp;
+mod models;
+mod network;
+mod pricing;
+mod remote;
+mod workspace;
+
+pub use agent::{AgentConfig, AgentToolsConfig, TrustLevel};
+pub use channels::ChannelsConfig;
+pub use display::DisplayConfig;
+pub use limits::LimitsConfig;
+pub use llm::{LlmConfig, OpenRouterConfig, OpenRouterRouteConfig};
+pub use lsp::LspConfig;
+pub use models::ModelsConfig;
+pub use network::NetworkConfig;
+pub use pricing::{ModelPrice, PricingConfig};
+pub use remote::{DefaultInteractionAction, RemoteConfig};
+pub use workspace::WorkspaceConfig;
+
+/// Root configuration document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub lsp: LspConfig,
+    pub channels: ChannelsConfig,
+    pub agent_tools: AgentToolsConfig,
+    pub remote: RemoteConfig,
+    pub models: ModelsConfig,
+    pub llm: LlmConfig,
+    pub pricing: PricingConfig,
+    pub limits: LimitsConfig,
+    pub workspace: WorkspaceConfig,
+    pub agent: AgentConfig,
+    pub display: DisplayConfig,
+    pub network: NetworkConfig,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the model capability database.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelsConfig {
+    /// When `true`, `init_models_db` never attempts a network fetch and
+    /// goes straight to cached → bundled, for offline/proxied
+    /// environments where the models.dev lookup would just time out.
+    pub disable_network_lookups: bool,
+}
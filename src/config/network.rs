@@ -0,0 +1,34 @@
+//! Outbound proxy and TLS configuration shared by the built-in LLM
+//! providers and the plugin host's HTTP client; see
+//! [`crate::llm::client::shared_client`] and
+//! [`crate::plugins::shared_blocking_client`]. Proxy fields each fall back
+//! to the matching standard environment variable when unset.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy URL for plain `http://` requests. Falls back to `HTTP_PROXY`.
+    pub http_proxy: Option<String>,
+    /// Proxy URL for `https://` requests. Falls back to `HTTPS_PROXY`.
+    pub https_proxy: Option<String>,
+    /// Comma-separated hosts/domains that bypass the proxy. Falls back to
+    /// `NO_PROXY`.
+    pub no_proxy: Option<String>,
+    /// Extra PEM-encoded CA certificates to trust, in addition to the
+    /// platform's default trust store. For self-hosted OpenAI-compatible
+    /// gateways (see `crate::llm::providers::openai_compat`) behind an
+    /// internal CA that isn't in the system trust store.
+    pub extra_ca_certs: Vec<PathBuf>,
+    /// Disables TLS certificate verification entirely for the shared HTTP
+    /// clients. This is a last-resort escape hatch for a gateway whose
+    /// certificate can't be fixed or added via `extra_ca_certs` (e.g. a
+    /// self-signed cert during local testing) — it defeats TLS's ability
+    /// to detect a tampered or impersonated connection, so it defaults to
+    /// `false` and should only be turned on deliberately, never as a
+    /// default deployment setting.
+    pub danger_accept_invalid_certs: bool,
+}
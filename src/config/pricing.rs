@@ -0,0 +1,28 @@
+//! Per-model price overrides, for self-hosted, Azure, or otherwise
+//! custom-priced models that [`crate::llm::models_db`]'s models.dev-backed
+//! pricing doesn't know about or gets wrong.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PricingConfig {
+    /// Overrides keyed by `"{provider}/{model}"`, taking precedence over
+    /// [`crate::llm::models_db::ModelsDb`]'s pricing for that key.
+    pub overrides: HashMap<String, ModelPrice>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelPrice {
+    pub input_cost_per_1m: f64,
+    pub output_cost_per_1m: f64,
+}
+
+impl PricingConfig {
+    pub fn get(&self, provider: &str, model: &str) -> Option<ModelPrice> {
+        self.overrides.get(&format!("{provider}/{model}")).copied()
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// What a pending interaction resolves to when nobody answers it in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultInteractionAction {
+    Cancel,
+    Deny,
+}
+
+/// Configuration for remote (channel-driven) sessions: how long a
+/// questionnaire or approval prompt waits for a human response before it
+/// is resolved automatically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteConfig {
+    /// Seconds a `PendingInteraction` stays open before the sweeper
+    /// resolves it to its default action.
+    pub interaction_timeout_secs: u64,
+
+    pub default_questionnaire_action: DefaultInteractionAction,
+    pub default_approval_action: DefaultInteractionAction,
+
+    /// How many times an invalid questionnaire answer is re-prompted
+    /// before the interaction is cancelled; see
+    /// [`crate::questionnaire::ask_until_valid`].
+    pub questionnaire_max_retries: usize,
+
+    /// Milliseconds between streamed-response edits; see
+    /// [`crate::channels::streaming::respond_streaming`]. Channels with a
+    /// tighter rate limit on message edits should raise this.
+    pub stream_debounce_ms: u64,
+
+    /// Buffered characters at which a streamed-response edit is sent right
+    /// away, without waiting for the next `stream_debounce_ms` tick (each
+    /// tick flushes whatever is buffered, however short); see
+    /// [`crate::channels::streaming::StreamCoalescer`]. Channels with a
+    /// high rate limit can lower this to stream more aggressively.
+    pub stream_min_chars: usize,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            interaction_timeout_secs: 300,
+            default_questionnaire_action: DefaultInteractionAction::Cancel,
+            default_approval_action: DefaultInteractionAction::Deny,
+            questionnaire_max_retries: 2,
+            stream_debounce_ms: 250,
+            stream_min_chars: 200,
+        }
+    }
+}
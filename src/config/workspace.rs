@@ -0,0 +1,13 @@
+//! Settings governing how tark walks the project tree: what to skip when
+//! searching, generating `tark explain`'s overview, or indexing symbols.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Extra gitignore-syntax patterns to skip, merged with any
+    /// `.tarkignore` file at the workspace root; see
+    /// [`crate::tools::ignore_rules::IgnoreRules`].
+    pub ignore_patterns: Vec<String>,
+}
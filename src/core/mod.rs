@@ -0,0 +1,20 @@
+//! Small utilities shared across modules that don't belong to any single
+//! domain (tools, LSP, channels, ...).
+
+pub mod proxy;
+pub mod timezone;
+pub mod tls;
+
+/// Truncates `s` to at most `max_bytes` bytes, backing off to the nearest
+/// earlier UTF-8 character boundary so the result is always valid `str`
+/// rather than panicking or silently splitting a multi-byte character.
+pub fn truncate_at_char_boundary(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut end = max_bytes;
+    while end > 0 && !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
@@ -0,0 +1,55 @@
+//! Resolves proxy settings that are shared between the async and blocking
+//! HTTP clients (`crate::llm::client`, `crate::plugins`) without tying
+//! either of those modules to the other.
+
+/// Resolves a single proxy setting: an explicit `config.network.*` value
+/// takes priority, falling back to the standard environment variable
+/// (`HTTP_PROXY`, `HTTPS_PROXY`, `NO_PROXY`) only when unset. An empty
+/// result resolves to `None`; an explicitly empty configured value does
+/// not fall back to the environment.
+pub fn resolve(configured: Option<&str>, env_var: &str) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var(env_var).ok())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Each test uses its own fake env var name so parallel test threads
+    // mutating process-global env state can't race each other.
+
+    #[test]
+    fn configured_value_wins_over_the_environment() {
+        unsafe { std::env::set_var("TARK_TEST_PROXY_CONFIGURED", "http://from-env") };
+        let resolved = resolve(Some("http://from-config"), "TARK_TEST_PROXY_CONFIGURED");
+        unsafe { std::env::remove_var("TARK_TEST_PROXY_CONFIGURED") };
+        assert_eq!(resolved.as_deref(), Some("http://from-config"));
+    }
+
+    #[test]
+    fn falls_back_to_the_environment_when_unconfigured() {
+        unsafe { std::env::set_var("TARK_TEST_PROXY_FALLBACK", "http://from-env") };
+        let resolved = resolve(None, "TARK_TEST_PROXY_FALLBACK");
+        unsafe { std::env::remove_var("TARK_TEST_PROXY_FALLBACK") };
+        assert_eq!(resolved.as_deref(), Some("http://from-env"));
+    }
+
+    #[test]
+    fn empty_configured_value_resolves_to_none_without_falling_back_to_the_environment() {
+        // `filter` runs after `or_else`, so an explicitly empty `configured`
+        // short-circuits to `None` rather than falling through to the env
+        // var - only an *unset* `configured` does that.
+        unsafe { std::env::set_var("TARK_TEST_PROXY_EMPTY", "http://from-env") };
+        let resolved = resolve(Some(""), "TARK_TEST_PROXY_EMPTY");
+        unsafe { std::env::remove_var("TARK_TEST_PROXY_EMPTY") };
+        assert_eq!(resolved, None);
+    }
+
+    #[test]
+    fn returns_none_when_neither_is_set() {
+        assert_eq!(resolve(None, "TARK_TEST_PROXY_NEVER_SET"), None);
+    }
+}
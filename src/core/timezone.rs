@@ -0,0 +1,55 @@
+//! Timezone-aware timestamp rendering driven by `config.display.timezone`.
+//! Storage stays UTC everywhere (session files, the audit log, ...); only
+//! the final render for a human — a channel's session header, a CLI
+//! table — goes through [`format_timestamp`].
+
+use chrono::{DateTime, Utc};
+
+/// Which zone a resolved `config.display.timezone` renders into.
+pub enum TimezoneChoice {
+    Utc,
+    Local,
+    Named(chrono_tz::Tz),
+}
+
+/// The result of resolving `config.display.timezone`: the zone to render
+/// with, plus a warning to surface to the user if the configured name
+/// wasn't recognized and UTC was substituted. This module doesn't print
+/// anything itself — see `cli::doctor::render`'s render/print split for
+/// why — so the caller decides where the warning goes.
+pub struct ResolvedTimezone {
+    pub zone: TimezoneChoice,
+    pub warning: Option<String>,
+}
+
+/// Resolves `configured` (an IANA name, or `"local"`/`"utc"`) to a
+/// [`TimezoneChoice`]. An unrecognized name falls back to UTC with a
+/// warning rather than failing outright, since a display preference isn't
+/// worth refusing to show anything over.
+pub fn resolve_timezone(configured: &str) -> ResolvedTimezone {
+    if configured.eq_ignore_ascii_case("utc") {
+        return ResolvedTimezone { zone: TimezoneChoice::Utc, warning: None };
+    }
+    if configured.eq_ignore_ascii_case("local") {
+        return ResolvedTimezone { zone: TimezoneChoice::Local, warning: None };
+    }
+    match configured.parse::<chrono_tz::Tz>() {
+        Ok(tz) => ResolvedTimezone { zone: TimezoneChoice::Named(tz), warning: None },
+        Err(_) => ResolvedTimezone {
+            zone: TimezoneChoice::Utc,
+            warning: Some(format!(
+                "config.display.timezone `{configured}` is not a recognized IANA timezone name; falling back to UTC"
+            )),
+        },
+    }
+}
+
+/// Formats `timestamp` (always stored/produced in UTC) for display in
+/// `zone`, without touching the underlying UTC value anywhere else.
+pub fn format_timestamp(timestamp: DateTime<Utc>, zone: &TimezoneChoice) -> String {
+    match zone {
+        TimezoneChoice::Utc => timestamp.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        TimezoneChoice::Local => timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        TimezoneChoice::Named(tz) => timestamp.with_timezone(tz).format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+    }
+}
@@ -0,0 +1,48 @@
+//! Structured event bus for agent lifecycle events, so the TUI, the HTTP
+//! server, and channel integrations can all subscribe to the same stream
+//! instead of each plumbing their own callbacks through the agent loop.
+
+use tokio::sync::broadcast;
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    TurnStarted { session_id: String },
+    TextDelta { session_id: String, text: String },
+    ToolCallStarted { session_id: String, tool: String },
+    ToolCallFinished { session_id: String, tool: String, ok: bool },
+    TurnFinished { session_id: String },
+    Error { session_id: String, message: String },
+}
+
+/// Broadcast bus: every subscriber gets every event. Subscribers that fall
+/// behind (slow TUI render, stalled HTTP client) lag rather than block
+/// publishers, per `tokio::sync::broadcast` semantics.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<AgentEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AgentEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publish an event. Errors (no subscribers) are intentionally
+    /// swallowed — publishing should never fail the agent turn that
+    /// triggered it.
+    pub fn publish(&self, event: AgentEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
@@ -0,0 +1,46 @@
+//! HTTP server (BFF): `/chat` and `/complete` endpoints, plus editor
+//! adapter routes (see `docs/BFF_ARCHITECTURE.md`).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+pub mod webhook;
+
+/// Tracks a [`CancellationToken`] per in-flight request so a client
+/// disconnect (or an explicit `/cancel/{id}` call) can stop the
+/// corresponding agent turn or completion instead of letting it run to
+/// completion unobserved.
+#[derive(Default)]
+pub struct RequestCancellationRegistry {
+    tokens: Mutex<HashMap<Uuid, CancellationToken>>,
+}
+
+impl RequestCancellationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self) -> (Uuid, CancellationToken) {
+        let id = Uuid::new_v4();
+        let token = CancellationToken::new();
+        self.tokens.lock().unwrap().insert(id, token.clone());
+        (id, token)
+    }
+
+    pub fn cancel(&self, id: Uuid) -> bool {
+        match self.tokens.lock().unwrap().get(&id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish(&self, id: Uuid) {
+        self.tokens.lock().unwrap().remove(&id);
+    }
+}
@@ -0,0 +1,193 @@
+//! `POST /channels/:plugin_id/webhook`: verifies an inbound webhook's HMAC
+//! signature against the plugin's configured secret, then dispatches it to
+//! a [`ChannelWebhookHandler`] so Slack/Discord-style webhooks can reach
+//! tark without each channel plugin running its own server.
+//!
+//! This module owns the verify-then-dispatch logic; wiring an actual HTTP
+//! framework route to [`route_webhook`] is left to the binary that embeds
+//! this crate, since this crate has no HTTP server framework dependency
+//! (axum, warp, ...) of its own — `crate::http` is a set of framework-
+//! agnostic handlers, not a server. The embedding binary's plugin manager
+//! (whatever tracks loaded channel plugins by id) is what implements
+//! [`ChannelWebhookHandler`] and extracts method/path/query/headers/body
+//! into a [`ChannelWebhookRequest`] from its chosen framework's request
+//! type before calling [`route_webhook`].
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::config::ChannelsConfig;
+
+/// Header most channel webhooks use to carry their request signature
+/// (hex-encoded HMAC-SHA256 of the raw body, keyed by the plugin's shared
+/// secret).
+pub const SIGNATURE_HEADER: &str = "x-tark-webhook-signature";
+
+#[derive(Debug, Clone)]
+pub struct ChannelWebhookRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelWebhookResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("no webhook secret configured for plugin `{0}`")]
+    NoSecret(String),
+    #[error("missing `{SIGNATURE_HEADER}` header")]
+    MissingSignature,
+    #[error("signature verification failed")]
+    InvalidSignature,
+}
+
+/// Implemented by whatever owns the set of loaded channel plugins, so this
+/// module doesn't need to know how a plugin id maps to a running
+/// instance.
+#[async_trait]
+pub trait ChannelWebhookHandler: Send + Sync {
+    async fn handle_webhook(&self, plugin_id: &str, request: ChannelWebhookRequest) -> ChannelWebhookResponse;
+}
+
+/// Verifies `request`'s signature against `plugin_id`'s configured secret
+/// and, on success, dispatches it to `handler`. Returns `Err` (and never
+/// calls `handler`) if no secret is configured or the signature doesn't
+/// match, so an unsigned or forged webhook can't reach a channel plugin.
+pub async fn route_webhook(
+    config: &ChannelsConfig,
+    plugin_id: &str,
+    request: ChannelWebhookRequest,
+    handler: &dyn ChannelWebhookHandler,
+) -> Result<ChannelWebhookResponse, WebhookError> {
+    let secret = config
+        .webhook_secrets
+        .get(plugin_id)
+        .ok_or_else(|| WebhookError::NoSecret(plugin_id.to_string()))?;
+    verify_signature(&request.body, request.headers.get(SIGNATURE_HEADER).map(String::as_str), secret)?;
+    Ok(handler.handle_webhook(plugin_id, request).await)
+}
+
+fn verify_signature(body: &[u8], signature_header: Option<&str>, secret: &str) -> Result<(), WebhookError> {
+    let signature = signature_header.ok_or(WebhookError::MissingSignature)?;
+    let expected = hmac_sha256_hex(secret.as_bytes(), body);
+    if constant_time_eq(signature.as_bytes(), expected.as_bytes()) {
+        Ok(())
+    } else {
+        Err(WebhookError::InvalidSignature)
+    }
+}
+
+fn hmac_sha256_hex(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Compares two byte strings in time independent of where they first
+/// differ, so signature verification doesn't leak a timing side channel
+/// an attacker could use to forge a valid signature byte by byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoHandler;
+
+    #[async_trait]
+    impl ChannelWebhookHandler for EchoHandler {
+        async fn handle_webhook(&self, plugin_id: &str, request: ChannelWebhookRequest) -> ChannelWebhookResponse {
+            ChannelWebhookResponse {
+                status: 200,
+                headers: HashMap::new(),
+                body: format!("{plugin_id}:{}", String::from_utf8_lossy(&request.body)).into_bytes(),
+            }
+        }
+    }
+
+    fn config_with_secret(plugin_id: &str, secret: &str) -> ChannelsConfig {
+        let mut config = ChannelsConfig::default();
+        config.webhook_secrets.insert(plugin_id.to_string(), secret.to_string());
+        config
+    }
+
+    fn request_with(body: &[u8], signature: Option<&str>) -> ChannelWebhookRequest {
+        let mut headers = HashMap::new();
+        if let Some(signature) = signature {
+            headers.insert(SIGNATURE_HEADER.to_string(), signature.to_string());
+        }
+        ChannelWebhookRequest {
+            method: "POST".to_string(),
+            path: "/channels/slack/webhook".to_string(),
+            query: HashMap::new(),
+            headers,
+            body: body.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatches_when_signature_matches() {
+        let config = config_with_secret("slack", "s3cr3t");
+        let body = b"hello";
+        let signature = hmac_sha256_hex(b"s3cr3t", body);
+        let request = request_with(body, Some(&signature));
+
+        let response = route_webhook(&config, "slack", request, &EchoHandler).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"slack:hello");
+    }
+
+    #[tokio::test]
+    async fn rejects_when_no_secret_is_configured() {
+        let config = ChannelsConfig::default();
+        let request = request_with(b"hello", Some("anything"));
+
+        let err = route_webhook(&config, "slack", request, &EchoHandler).await.unwrap_err();
+        assert!(matches!(err, WebhookError::NoSecret(plugin) if plugin == "slack"));
+    }
+
+    #[tokio::test]
+    async fn rejects_when_signature_header_is_missing() {
+        let config = config_with_secret("slack", "s3cr3t");
+        let request = request_with(b"hello", None);
+
+        let err = route_webhook(&config, "slack", request, &EchoHandler).await.unwrap_err();
+        assert!(matches!(err, WebhookError::MissingSignature));
+    }
+
+    #[tokio::test]
+    async fn rejects_when_signature_does_not_match() {
+        let config = config_with_secret("slack", "s3cr3t");
+        let request = request_with(b"hello", Some("deadbeef"));
+
+        let err = route_webhook(&config, "slack", request, &EchoHandler).await.unwrap_err();
+        assert!(matches!(err, WebhookError::InvalidSignature));
+    }
+
+    #[tokio::test]
+    async fn rejects_when_body_was_tampered_with_after_signing() {
+        let config = config_with_secret("slack", "s3cr3t");
+        let signature = hmac_sha256_hex(b"s3cr3t", b"original");
+        let request = request_with(b"tampered", Some(&signature));
+
+        let err = route_webhook(&config, "slack", request, &EchoHandler).await.unwrap_err();
+        assert!(matches!(err, WebhookError::InvalidSignature));
+    }
+}
@@ -0,0 +1,21 @@
+//! tark: AI-powered CLI agent with TUI chat interface and editor integration.
+
+pub mod agent;
+pub mod approval;
+pub mod channels;
+pub mod cli;
+pub mod completion;
+pub mod config;
+pub mod core;
+pub mod events;
+pub mod http;
+pub mod llm;
+pub mod lsp;
+pub mod mcp;
+pub mod plugins;
+pub mod prompt;
+pub mod questionnaire;
+pub mod security;
+pub mod session;
+pub mod tools;
+pub mod usage;
@@ -0,0 +1,217 @@
+//! Health-gated circuit breaker for LLM providers.
+//!
+//! Providers that fail repeatedly (timeouts, 5xx, connection errors) are
+//! disabled for a cooldown period instead of being retried on every
+//! request, then automatically re-enabled for a trial request once the
+//! cooldown elapses. [`CircuitBreakerProvider`] wraps any [`LlmProvider`]
+//! with this state machine so the gating actually happens on the request
+//! path instead of living as a standalone type nothing calls.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::llm::error::LlmError;
+use crate::llm::{LlmProvider, LlmResponse};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    /// Cooldown elapsed; the next request is let through as a health probe.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown => CircuitState::HalfOpen,
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Whether a request should be attempted right now.
+    pub fn allow_request(&self) -> bool {
+        !matches!(self.state(), CircuitState::Open)
+    }
+
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= self.failure_threshold {
+            self.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Gates a wrapped [`LlmProvider`] behind a [`CircuitBreaker`]: a request
+/// is refused with [`LlmError::CircuitOpen`] while the breaker is open,
+/// and every attempted request (including the half-open trial) records
+/// success or failure back into the breaker. `&self`-only `chat` means the
+/// breaker's mutable state lives behind a `Mutex`, the same pattern
+/// `ReplayProvider` uses for its own interior state.
+pub struct CircuitBreakerProvider {
+    inner: Box<dyn LlmProvider>,
+    provider_name: String,
+    breaker: Mutex<CircuitBreaker>,
+}
+
+impl CircuitBreakerProvider {
+    pub fn new(inner: Box<dyn LlmProvider>, provider_name: impl Into<String>, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            inner,
+            provider_name: provider_name.into(),
+            breaker: Mutex::new(CircuitBreaker::new(failure_threshold, cooldown)),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for CircuitBreakerProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        if !self.breaker.lock().unwrap().allow_request() {
+            return Err(LlmError::CircuitOpen {
+                provider: self.provider_name.clone(),
+            }
+            .into());
+        }
+
+        match self.inner.chat(prompt).await {
+            Ok(response) => {
+                self.breaker.lock().unwrap().record_success();
+                Ok(response)
+            }
+            Err(err) => {
+                self.breaker.lock().unwrap().record_failure();
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn closed_until_failure_threshold_is_reached() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_request());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn reopens_half_open_once_cooldown_elapses() {
+        let mut breaker = CircuitBreaker::new(1, Duration::from_millis(0));
+        breaker.record_failure();
+        // A zero cooldown elapses immediately, so the very next check sees
+        // HalfOpen rather than Open.
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.allow_request());
+    }
+
+    /// A provider whose `chat` fails while `fail` is set, shared via `Arc`
+    /// so a test can flip it after constructing the provider, to drive
+    /// `CircuitBreakerProvider` through both halves of the state machine.
+    struct ToggleProvider {
+        fail: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl LlmProvider for ToggleProvider {
+        async fn chat(&self, _prompt: &str) -> anyhow::Result<LlmResponse> {
+            if self.fail.load(Ordering::SeqCst) {
+                anyhow::bail!("simulated upstream failure")
+            } else {
+                Ok(LlmResponse::default())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_provider_refuses_requests_once_open() {
+        let fail = Arc::new(AtomicBool::new(true));
+        let provider = CircuitBreakerProvider::new(
+            Box::new(ToggleProvider { fail }),
+            "test-provider",
+            2,
+            Duration::from_secs(60),
+        );
+
+        assert!(provider.chat("hi").await.is_err());
+        assert!(provider.chat("hi").await.is_err());
+
+        // The breaker is now open: the next call should be refused with
+        // CircuitOpen and never reach the inner provider.
+        let err = provider.chat("hi").await.unwrap_err();
+        let llm_err = err.downcast_ref::<LlmError>().expect("circuit-open error should be an LlmError");
+        assert!(matches!(llm_err, LlmError::CircuitOpen { .. }));
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_provider_recovers_after_successful_trial() {
+        let fail = Arc::new(AtomicBool::new(true));
+        let provider = CircuitBreakerProvider::new(
+            Box::new(ToggleProvider { fail: fail.clone() }),
+            "test-provider",
+            1,
+            Duration::from_millis(0),
+        );
+
+        assert!(provider.chat("hi").await.is_err());
+
+        // Cooldown is zero, so the breaker is half-open immediately; a
+        // successful trial request should close it again and subsequent
+        // requests should reach the inner provider rather than being
+        // refused.
+        fail.store(false, Ordering::SeqCst);
+        assert!(provider.chat("hi").await.is_ok());
+
+        fail.store(true, Ordering::SeqCst);
+        assert!(provider.chat("hi").await.is_err());
+    }
+}
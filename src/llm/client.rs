@@ -0,0 +1,45 @@
+//! A process-wide shared `reqwest::Client` for the built-in LLM providers.
+//!
+//! Each provider used to be handed its own freshly-built client; sharing
+//! one instead means every provider reuses the same connection pool, so
+//! repeated calls to the same (or different) provider don't each pay a
+//! fresh TCP/TLS handshake. Providers still set per-request state (bearer
+//! tokens, per-call JSON bodies) on the request itself — only the
+//! underlying connector/pool, and the proxy settings below, are shared.
+
+use std::sync::OnceLock;
+
+use crate::config::NetworkConfig;
+use crate::core::{proxy, tls};
+
+static SHARED_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// Returns the shared client, building it (applying `network`'s proxy
+/// settings) on first use. Only the first caller's `network` takes effect,
+/// since the client is a process-wide singleton — this matches how a
+/// deployment's proxy configuration is fixed for the life of the process.
+pub fn shared_client(network: &NetworkConfig) -> reqwest::Client {
+    SHARED_CLIENT.get_or_init(|| build_client(network)).clone()
+}
+
+fn build_client(network: &NetworkConfig) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder();
+    let no_proxy = proxy::resolve(network.no_proxy.as_deref(), "NO_PROXY");
+
+    if let Some(url) = proxy::resolve(network.http_proxy.as_deref(), "HTTP_PROXY") {
+        if let Ok(mut http_proxy) = reqwest::Proxy::http(&url) {
+            http_proxy = http_proxy.no_proxy(no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            builder = builder.proxy(http_proxy);
+        }
+    }
+    if let Some(url) = proxy::resolve(network.https_proxy.as_deref(), "HTTPS_PROXY") {
+        if let Ok(mut https_proxy) = reqwest::Proxy::https(&url) {
+            https_proxy = https_proxy.no_proxy(no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            builder = builder.proxy(https_proxy);
+        }
+    }
+
+    builder = tls::apply(builder, network);
+
+    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+}
@@ -0,0 +1,175 @@
+//! Structured errors surfaced by LLM providers. Distinct from the
+//! `anyhow::Result` used for most provider plumbing so callers can match
+//! on a specific failure mode and show the user a concrete next step
+//! (e.g. "run `tark auth gemini`") instead of an opaque status code or a
+//! raw JSON error body.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("rate limited by `{provider}`")]
+    RateLimited {
+        provider: String,
+        /// Seconds to wait before retrying, when the provider reported one
+        /// (e.g. a `Retry-After` header).
+        retry_after: Option<u64>,
+        hint: Option<String>,
+    },
+
+    #[error("authentication failed for `{provider}`")]
+    AuthFailed { provider: String, hint: Option<String> },
+
+    /// The provider's credentials were valid but have since expired, and a
+    /// refresh either wasn't possible or wasn't configured; distinct from
+    /// [`LlmError::AuthFailed`] because the fix is "refresh/re-auth", not
+    /// "the stored credentials are wrong".
+    #[error("authentication expired for `{provider}`; re-authenticate with `{reauth_command}`")]
+    AuthExpired {
+        provider: String,
+        reauth_command: String,
+    },
+
+    #[error("context too long for `{provider}` (limit: {limit} tokens)")]
+    ContextTooLong { provider: String, limit: usize },
+
+    #[error("model not found for `{provider}`")]
+    ModelNotFound { provider: String, hint: Option<String> },
+
+    #[error("network error talking to `{provider}`: {message}")]
+    Network { provider: String, message: String },
+
+    #[error("`{provider}` returned a server error (status {status})")]
+    ServerError { provider: String, status: u16 },
+
+    #[error("bad request to `{provider}`: {message}")]
+    BadRequest { provider: String, message: String },
+
+    /// `{provider}`'s [`crate::llm::circuit_breaker::CircuitBreaker`] has
+    /// tripped after repeated failures and is cooling down; the request
+    /// was never sent.
+    #[error("`{provider}` is temporarily disabled after repeated failures")]
+    CircuitOpen { provider: String },
+}
+
+impl LlmError {
+    /// The provider this error came from, for callers that want to
+    /// attribute it without matching on every variant.
+    pub fn provider(&self) -> &str {
+        match self {
+            LlmError::RateLimited { provider, .. }
+            | LlmError::AuthFailed { provider, .. }
+            | LlmError::AuthExpired { provider, .. }
+            | LlmError::ContextTooLong { provider, .. }
+            | LlmError::ModelNotFound { provider, .. }
+            | LlmError::Network { provider, .. }
+            | LlmError::ServerError { provider, .. }
+            | LlmError::BadRequest { provider, .. }
+            | LlmError::CircuitOpen { provider } => provider,
+        }
+    }
+
+    /// A short, user-facing suggestion for what to do about this error, if
+    /// one applies. The agent and channel integrations should render this
+    /// alongside (or instead of) the raw error message.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            LlmError::RateLimited { hint, retry_after, .. } => hint.clone().or_else(|| {
+                retry_after.map(|secs| format!("wait {secs}s before retrying"))
+            }),
+            LlmError::AuthFailed { provider, hint } => {
+                hint.clone().or_else(|| Some(format!("run `tark auth {provider}`")))
+            }
+            LlmError::AuthExpired { reauth_command, .. } => Some(format!("run `{reauth_command}`")),
+            LlmError::ContextTooLong { .. } => {
+                Some("shorten the conversation or start a new session".to_string())
+            }
+            LlmError::ModelNotFound { hint, .. } => hint.clone(),
+            LlmError::Network { .. } => Some("check your connection and retry".to_string()),
+            LlmError::ServerError { .. } => Some("the provider is likely having an outage; retry later".to_string()),
+            LlmError::BadRequest { .. } => None,
+            LlmError::CircuitOpen { .. } => {
+                Some("wait for the cooldown to elapse or switch providers".to_string())
+            }
+        }
+    }
+}
+
+/// Classifies an HTTP error response from a provider into an [`LlmError`]
+/// variant. Shared by every provider's error handling so a 429 from
+/// OpenRouter and a 429 from Copilot end up in the same bucket.
+pub fn from_status(provider: &str, status: reqwest::StatusCode, body: &str) -> LlmError {
+    let provider = provider.to_string();
+    match status.as_u16() {
+        401 | 403 => LlmError::AuthFailed {
+            hint: Some(format!("run `tark auth {provider}`")),
+            provider,
+        },
+        404 => LlmError::ModelNotFound {
+            hint: Some(format!("check the configured model id for `{provider}`")),
+            provider,
+        },
+        429 => LlmError::RateLimited {
+            provider,
+            retry_after: None,
+            hint: None,
+        },
+        400 => match context_limit_from_body(body) {
+            Some(limit) => LlmError::ContextTooLong { provider, limit },
+            None => LlmError::BadRequest {
+                provider,
+                message: body.to_string(),
+            },
+        },
+        status if status >= 500 => LlmError::ServerError {
+            provider,
+            status,
+        },
+        _ => LlmError::Network {
+            provider,
+            message: format!("unexpected status {status}: {body}"),
+        },
+    }
+}
+
+/// Classifies a failed `send()` call — the request never got an HTTP
+/// response at all, whether because it timed out or the connection itself
+/// failed — into an [`LlmError::Network`]. The counterpart to
+/// [`from_status`] for when there's no status code to classify by.
+pub fn from_send_error(provider: &str, err: reqwest::Error) -> LlmError {
+    let message = if err.is_timeout() {
+        "request timed out".to_string()
+    } else {
+        err.to_string()
+    };
+    LlmError::Network {
+        provider: provider.to_string(),
+        message,
+    }
+}
+
+/// Renders a provider failure for display to the user: if `err` is a
+/// downcastable [`LlmError`], its remediation hint (e.g. "run `tark auth
+/// gemini`") is appended, rather than letting the channel or CLI print the
+/// raw status/JSON the provider returned.
+pub fn render_for_user(err: &anyhow::Error) -> String {
+    match err.downcast_ref::<LlmError>() {
+        Some(llm_err) => match llm_err.hint() {
+            Some(hint) => format!("{llm_err}\n\nhint: {hint}"),
+            None => llm_err.to_string(),
+        },
+        None => err.to_string(),
+    }
+}
+
+/// Looks for a token-limit number in a "maximum context length is N
+/// tokens"-style error body. Providers phrase this differently, but they
+/// all include the limit as a bare number somewhere in the message.
+fn context_limit_from_body(body: &str) -> Option<usize> {
+    if !body.to_ascii_lowercase().contains("context") {
+        return None;
+    }
+    body.split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .find_map(|s| s.parse::<usize>().ok())
+}
@@ -0,0 +1,150 @@
+//! LLM provider abstractions. Each concrete provider (Claude, OpenAI,
+//! Gemini, Ollama, plugin-backed, ...) implements [`LlmProvider`].
+
+use async_trait::async_trait;
+
+use crate::llm::streaming::{StopOnToolCall, StreamEvent};
+
+/// A single streamed chunk of an in-progress chat response.
+#[derive(Debug, Clone)]
+pub struct StreamChunk {
+    pub delta: String,
+}
+
+/// A tool invocation the model asked for as part of a chat response.
+#[derive(Debug, Clone)]
+pub struct ToolCallRequest {
+    /// Provider-assigned call id, echoed back when the tool result is fed
+    /// into the next turn.
+    pub id: String,
+    pub name: String,
+    pub args: serde_json::Value,
+}
+
+/// The outcome of a non-streaming chat call.
+#[derive(Debug, Clone, Default)]
+pub struct LlmResponse {
+    pub text: String,
+    /// Tool calls the model wants executed before it continues. Empty for
+    /// a plain text response.
+    pub tool_calls: Vec<ToolCallRequest>,
+    /// Which upstream actually served the request, for providers that
+    /// route across multiple backends (e.g. OpenRouter's `provider` field
+    /// in its response body). `None` for providers that talk to a single
+    /// fixed backend.
+    pub served_by: Option<String>,
+    /// Token usage for this turn. Populated from the provider's own
+    /// accounting when available; [`LlmProvider::chat_streaming_with_thinking`]
+    /// fills in an estimate (flagged via [`TokenUsage::estimated`]) for
+    /// providers/paths that don't report it.
+    pub usage: Option<TokenUsage>,
+}
+
+/// Token counts for a single turn, from either the provider's own
+/// accounting or an estimate when the provider omitted it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// `true` when these counts were estimated locally (e.g. from
+    /// character counts) rather than reported by the provider.
+    pub estimated: bool,
+}
+
+impl TokenUsage {
+    /// Combines usage from another turn into this one, e.g. across
+    /// multiple provider calls within one agent run. Once either side is
+    /// estimated, the combined total is considered estimated too.
+    pub fn merge(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.estimated = self.estimated || other.estimated;
+    }
+}
+
+/// Rough fallback used when a provider doesn't report usage for a turn,
+/// mirroring the heuristic `crate::agent::compact_transcript` uses elsewhere
+/// in the absence of a real tokenizer.
+const CHARS_PER_TOKEN_ESTIMATE: usize = 4;
+
+fn estimate_usage(prompt: &str, output: &str) -> TokenUsage {
+    TokenUsage {
+        input_tokens: (prompt.len() / CHARS_PER_TOKEN_ESTIMATE) as u64,
+        output_tokens: (output.len() / CHARS_PER_TOKEN_ESTIMATE) as u64,
+        estimated: true,
+    }
+}
+
+/// Common interface implemented by every LLM backend.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse>;
+
+    /// Whether this provider can stream incremental output. Providers that
+    /// only support buffered responses should return `false` and leave
+    /// [`LlmProvider::chat_streaming`] at its default, buffered fallback.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Whether this provider's stream needs to be closed client-side once
+    /// all announced tool calls complete, to avoid paying for generation
+    /// the agent will discard. Defaults to `ProviderStops` since most
+    /// providers end the stream at their own `finish_reason` boundary;
+    /// override for providers observed to keep emitting tokens after a
+    /// tool call is fully formed.
+    fn stop_on_tool_call(&self) -> StopOnToolCall {
+        StopOnToolCall::ProviderStops
+    }
+
+    /// Stream a chat response, invoking `on_chunk` for each delta. The
+    /// default implementation falls back to [`LlmProvider::chat`] and
+    /// delivers the whole response as a single chunk.
+    async fn chat_streaming(
+        &self,
+        prompt: &str,
+        on_chunk: &mut (dyn FnMut(StreamChunk) + Send),
+    ) -> anyhow::Result<LlmResponse> {
+        let response = self.chat(prompt).await?;
+        on_chunk(StreamChunk {
+            delta: response.text.clone(),
+        });
+        Ok(response)
+    }
+
+    /// Like [`LlmProvider::chat_streaming`], but delivers the richer
+    /// [`StreamEvent`] set (thinking deltas, tool call progress, and a
+    /// terminal [`StreamEvent::Usage`] once the provider reports it)
+    /// instead of plain text chunks. The default implementation falls back
+    /// to `chat_streaming` and, if the resulting response has no usage
+    /// (the provider never emitted one), fills in an estimate so callers
+    /// can always rely on `LlmResponse.usage` being populated.
+    async fn chat_streaming_with_thinking(
+        &self,
+        prompt: &str,
+        on_event: &mut (dyn FnMut(StreamEvent) + Send),
+    ) -> anyhow::Result<LlmResponse> {
+        let mut response = self
+            .chat_streaming(prompt, &mut |chunk| on_event(StreamEvent::TextDelta(chunk.delta)))
+            .await?;
+        if let Some(usage) = response.usage {
+            on_event(StreamEvent::Usage(usage));
+        } else {
+            let usage = estimate_usage(prompt, &response.text);
+            on_event(StreamEvent::Usage(usage));
+            response.usage = Some(usage);
+        }
+        Ok(response)
+    }
+}
+
+pub mod circuit_breaker;
+pub mod client;
+pub mod error;
+pub mod model_selector;
+pub mod models_db;
+pub mod providers;
+pub mod raw_log;
+pub mod replay;
+pub mod plugin_provider;
+pub mod streaming;
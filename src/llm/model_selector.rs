@@ -0,0 +1,186 @@
+//! Downshifts to a cheaper configured model once projected session spend
+//! crosses a soft threshold, rather than stopping outright like
+//! [`crate::usage::limits::SpendLimiter`]'s hard limit does.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::config::LimitsConfig;
+use crate::llm::models_db::ModelsDb;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DownshiftError {
+    #[error("downshift model `{0}` is not in the models database")]
+    UnknownModel(String),
+    #[error("downshift model `{0}` doesn't support tool calling, which this session needs")]
+    MissingTools(String),
+    #[error("`downshift_model` must be \"provider/model\", got `{0}`")]
+    MalformedModelId(String),
+}
+
+/// The outcome of [`ModelSelector::maybe_downshift`] once a session has
+/// crossed `soft_limit_usd`: which model subsequent turns should use, and
+/// whether this is the first turn it applies to (so the caller can
+/// announce the switch to the user exactly once).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Downshift {
+    pub model_id: String,
+    pub newly_crossed: bool,
+}
+
+/// Once a session crosses `soft_limit_usd`, points every subsequent turn
+/// at `downshift_model` instead. Tracks which sessions have already
+/// crossed the threshold so the switch stays in effect (rather than
+/// reverting to the expensive model) on every call after the first, while
+/// still letting the caller tell the first crossing apart from later ones.
+pub struct ModelSelector {
+    limits: LimitsConfig,
+    crossed: Mutex<HashSet<String>>,
+}
+
+impl ModelSelector {
+    pub fn new(limits: LimitsConfig) -> Self {
+        Self {
+            limits,
+            crossed: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Checks whether `session_id`'s `projected_total_usd` (spend so far
+    /// plus the next call's estimated cost) should trigger a downshift.
+    /// Returns `Ok(None)` when no downshift is configured or the
+    /// threshold hasn't been crossed. Once crossed, every subsequent call
+    /// for that `session_id` returns `Ok(Some(downshift))` — including
+    /// this and all later turns — with `newly_crossed` true only the first
+    /// time, so the caller can announce the switch once but keep routing
+    /// to the cheaper model for the rest of the session. Returns an error
+    /// if the configured downshift model can't actually serve this
+    /// session (unknown to `models_db`, or missing `tools` support when
+    /// `requires_tools` is set).
+    pub fn maybe_downshift(
+        &self,
+        session_id: &str,
+        projected_total_usd: f64,
+        requires_tools: bool,
+        models_db: &ModelsDb,
+    ) -> Result<Option<Downshift>, DownshiftError> {
+        let Some(soft_limit) = self.limits.soft_limit_usd else {
+            return Ok(None);
+        };
+        let Some(model_id) = &self.limits.downshift_model else {
+            return Ok(None);
+        };
+
+        let mut crossed = self.crossed.lock().unwrap();
+        let already_crossed = crossed.contains(session_id);
+        if !already_crossed && projected_total_usd < soft_limit {
+            return Ok(None);
+        }
+
+        let (provider, model) = model_id
+            .split_once('/')
+            .ok_or_else(|| DownshiftError::MalformedModelId(model_id.clone()))?;
+        let capabilities = models_db
+            .capabilities(provider, model)
+            .ok_or_else(|| DownshiftError::UnknownModel(model_id.clone()))?;
+        if requires_tools && !capabilities.tools {
+            return Err(DownshiftError::MissingTools(model_id.clone()));
+        }
+
+        let newly_crossed = !already_crossed;
+        if newly_crossed {
+            crossed.insert(session_id.to_string());
+        }
+        Ok(Some(Downshift {
+            model_id: model_id.clone(),
+            newly_crossed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::models_db::{ModelCapabilities, ModelEntry};
+
+    fn models_db_with(provider: &str, model: &str, tools: bool) -> ModelsDb {
+        let mut db = ModelsDb::new();
+        db.insert(ModelEntry {
+            id: model.to_string(),
+            provider: provider.to_string(),
+            capabilities: ModelCapabilities {
+                tools,
+                ..Default::default()
+            },
+        });
+        db
+    }
+
+    fn limits(soft_limit_usd: f64, downshift_model: &str) -> LimitsConfig {
+        LimitsConfig {
+            soft_limit_usd: Some(soft_limit_usd),
+            downshift_model: Some(downshift_model.to_string()),
+        }
+    }
+
+    #[test]
+    fn no_downshift_below_threshold() {
+        let selector = ModelSelector::new(limits(10.0, "openai/gpt-4o-mini"));
+        let db = models_db_with("openai", "gpt-4o-mini", true);
+        assert_eq!(selector.maybe_downshift("session-1", 5.0, false, &db).unwrap(), None);
+    }
+
+    #[test]
+    fn crossing_soft_threshold_flips_the_model_used_on_the_next_call() {
+        let selector = ModelSelector::new(limits(10.0, "openai/gpt-4o-mini"));
+        let db = models_db_with("openai", "gpt-4o-mini", true);
+
+        let first = selector.maybe_downshift("session-1", 11.0, false, &db).unwrap();
+        assert_eq!(
+            first,
+            Some(Downshift {
+                model_id: "openai/gpt-4o-mini".to_string(),
+                newly_crossed: true,
+            })
+        );
+
+        // The next turn is well under the threshold on its own, but the
+        // session already crossed it once — it must keep routing to the
+        // downshifted model instead of reverting to the expensive one.
+        let second = selector.maybe_downshift("session-1", 0.0, false, &db).unwrap();
+        assert_eq!(
+            second,
+            Some(Downshift {
+                model_id: "openai/gpt-4o-mini".to_string(),
+                newly_crossed: false,
+            })
+        );
+    }
+
+    #[test]
+    fn unrelated_session_is_unaffected_by_another_sessions_crossing() {
+        let selector = ModelSelector::new(limits(10.0, "openai/gpt-4o-mini"));
+        let db = models_db_with("openai", "gpt-4o-mini", true);
+
+        selector.maybe_downshift("session-1", 11.0, false, &db).unwrap();
+        assert_eq!(selector.maybe_downshift("session-2", 0.0, false, &db).unwrap(), None);
+    }
+
+    #[test]
+    fn errors_when_downshift_model_lacks_required_tool_support() {
+        let selector = ModelSelector::new(limits(10.0, "openai/gpt-4o-mini"));
+        let db = models_db_with("openai", "gpt-4o-mini", false);
+
+        let err = selector.maybe_downshift("session-1", 11.0, true, &db).unwrap_err();
+        assert!(matches!(err, DownshiftError::MissingTools(_)));
+    }
+
+    #[test]
+    fn errors_on_malformed_model_id() {
+        let selector = ModelSelector::new(limits(10.0, "not-a-provider-slash-model"));
+        let db = ModelsDb::new();
+
+        let err = selector.maybe_downshift("session-1", 11.0, false, &db).unwrap_err();
+        assert!(matches!(err, DownshiftError::MalformedModelId(_)));
+    }
+}
@@ -0,0 +1,138 @@
+//! Database of known models and their capabilities, used to decide what
+//! UI/tooling to offer (vision attachments, tool calling, extended
+//! thinking) without a round-trip to the provider.
+//!
+//! Populated, in order of preference, from: a live fetch from models.dev
+//! (unless `ModelsConfig::disable_network_lookups` is set), a local cache
+//! refreshed from it (`tark models refresh`), or a snapshot bundled into
+//! the binary so capability detection still works offline.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    pub vision: bool,
+    pub tools: bool,
+    pub thinking: bool,
+    pub max_context_tokens: u32,
+    /// USD per 1M input/output tokens, as reported by models.dev. Missing
+    /// for entries fetched before pricing was tracked, or for providers
+    /// (Ollama, self-hosted) models.dev has no price for.
+    #[serde(default)]
+    pub input_cost_per_1m: f64,
+    #[serde(default)]
+    pub output_cost_per_1m: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelEntry {
+    pub id: String,
+    pub provider: String,
+    pub capabilities: ModelCapabilities,
+}
+
+/// Registry of known models, keyed by `"{provider}/{model_id}"`.
+#[derive(Debug, Clone, Default)]
+pub struct ModelsDb {
+    entries: HashMap<String, ModelEntry>,
+}
+
+impl ModelsDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, entry: ModelEntry) {
+        self.entries.insert(format!("{}/{}", entry.provider, entry.id), entry);
+    }
+
+    pub fn capabilities(&self, provider: &str, model_id: &str) -> Option<&ModelCapabilities> {
+        self.entries
+            .get(&format!("{provider}/{model_id}"))
+            .map(|e| &e.capabilities)
+    }
+
+    pub fn supports(&self, provider: &str, model_id: &str, feature: impl Fn(&ModelCapabilities) -> bool) -> bool {
+        self.capabilities(provider, model_id).is_some_and(feature)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ModelEntry> {
+        self.entries.values()
+    }
+
+    fn from_entries(entries: Vec<ModelEntry>) -> Self {
+        let mut db = Self::new();
+        for entry in entries {
+            db.insert(entry);
+        }
+        db
+    }
+}
+
+/// Snapshot compiled into the binary so capability detection keeps
+/// working with no network access at all.
+const BUNDLED_MODELS_JSON: &str = include_str!("models_bundled.json");
+
+/// Where `init_models_db` ended up loading its entries from, logged so an
+/// operator can tell why capability detection might be stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelsDbSource {
+    /// Freshly fetched from models.dev this call.
+    Network,
+    /// Loaded from the `~/.config/tark/models.json` cache written by a
+    /// previous `tark models refresh`.
+    Cached,
+    /// Fell back to the snapshot compiled into the binary.
+    Bundled,
+}
+
+fn bundled_models_db() -> ModelsDb {
+    let entries: Vec<ModelEntry> =
+        serde_json::from_str(BUNDLED_MODELS_JSON).expect("bundled models.json is valid");
+    ModelsDb::from_entries(entries)
+}
+
+fn load_cache(cache_path: &Path) -> Option<ModelsDb> {
+    let contents = std::fs::read_to_string(cache_path).ok()?;
+    let entries: Vec<ModelEntry> = serde_json::from_str(&contents).ok()?;
+    Some(ModelsDb::from_entries(entries))
+}
+
+/// Writes `db`'s entries to `cache_path` as the format `init_models_db`
+/// and `load_cache` expect, creating the parent directory if needed.
+pub fn save_cache(cache_path: &Path, db: &ModelsDb) -> std::io::Result<()> {
+    if let Some(parent) = cache_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let entries: Vec<&ModelEntry> = db.entries().collect();
+    std::fs::write(cache_path, serde_json::to_string_pretty(&entries)?)
+}
+
+/// Builds a `ModelsDb` for startup, preferring (unless network lookups are
+/// disabled) a fresh fetch via `fetch_latest`, then the on-disk cache at
+/// `cache_path`, then the bundled snapshot — so the CLI stays usable
+/// offline or behind a proxy that blocks the live lookup.
+pub async fn init_models_db<F, Fut>(
+    cache_path: &Path,
+    disable_network_lookups: bool,
+    fetch_latest: F,
+) -> (ModelsDb, ModelsDbSource)
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<Vec<ModelEntry>>>,
+{
+    if !disable_network_lookups {
+        if let Ok(entries) = fetch_latest().await {
+            let db = ModelsDb::from_entries(entries);
+            let _ = save_cache(cache_path, &db);
+            return (db, ModelsDbSource::Network);
+        }
+    }
+    if let Some(db) = load_cache(cache_path) {
+        return (db, ModelsDbSource::Cached);
+    }
+    (bundled_models_db(), ModelsDbSource::Bundled)
+}
@@ -0,0 +1,71 @@
+//! LLM provider backed by a WASM `provider` plugin (see
+//! `docs/PLUGIN_SDK.md`).
+//!
+//! Plugins export `provider_chat` for buffered responses. Plugins built
+//! against a newer SDK may additionally export `provider_chat_stream`,
+//! which writes chunks through the host-provided `tark:stream` function
+//! instead of returning the whole response at once. When the streaming
+//! export is absent we transparently fall back to the buffered call.
+
+use async_trait::async_trait;
+
+use crate::llm::{LlmProvider, LlmResponse, StreamChunk};
+
+/// Handle to a loaded provider plugin instance.
+pub struct PluginProvider {
+    instance: crate::plugins::PluginInstance,
+}
+
+impl PluginProvider {
+    pub fn new(instance: crate::plugins::PluginInstance) -> Self {
+        Self { instance }
+    }
+
+    /// True if the plugin module exports `provider_chat_stream`.
+    fn has_streaming_export(&self) -> bool {
+        self.instance.exports_function("provider_chat_stream")
+    }
+}
+
+#[async_trait]
+impl LlmProvider for PluginProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        let text = self.instance.call_provider_chat(prompt)?;
+        Ok(LlmResponse {
+            text,
+            ..Default::default()
+        })
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.has_streaming_export()
+    }
+
+    async fn chat_streaming(
+        &self,
+        prompt: &str,
+        on_chunk: &mut (dyn FnMut(StreamChunk) + Send),
+    ) -> anyhow::Result<LlmResponse> {
+        if !self.has_streaming_export() {
+            return LlmProvider::chat(self, prompt)
+                .await
+                .inspect(|response| {
+                    on_chunk(StreamChunk {
+                        delta: response.text.clone(),
+                    });
+                });
+        }
+
+        let mut full = String::new();
+        self.instance.call_provider_chat_stream(prompt, &mut |chunk: &str| {
+            full.push_str(chunk);
+            on_chunk(StreamChunk {
+                delta: chunk.to_string(),
+            });
+        })?;
+        Ok(LlmResponse {
+            text: full,
+            ..Default::default()
+        })
+    }
+}
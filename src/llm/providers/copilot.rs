@@ -0,0 +1,176 @@
+//! GitHub Copilot provider. Copilot's device-flow access token is short
+//! lived, so this provider tracks its expiry and refreshes proactively
+//! ahead of time, with a single refresh-and-retry on an unexpected 401 as
+//! a fallback for the race where a request is already in flight when the
+//! token lapses.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+
+use crate::llm::error::LlmError;
+use crate::llm::{LlmProvider, LlmResponse};
+
+const DEFAULT_BASE_URL: &str = "https://api.githubcopilot.com";
+
+/// A Copilot access token plus when it stops being valid.
+#[derive(Debug, Clone)]
+pub struct CopilotToken {
+    pub access_token: String,
+    pub expires_at: Instant,
+}
+
+impl CopilotToken {
+    /// Refresh this far ahead of the real expiry so a request that starts
+    /// just before the deadline doesn't race it.
+    const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+    fn needs_refresh(&self) -> bool {
+        Instant::now() + Self::REFRESH_MARGIN >= self.expires_at
+    }
+}
+
+/// Fetches a fresh Copilot token, typically by exchanging the GitHub OAuth
+/// token stored by `tark auth copilot`. Boxed so tests can substitute a
+/// fake that hands back an already-expiring token to exercise the refresh
+/// path without a real clock.
+pub type TokenRefresher =
+    Box<dyn Fn() -> BoxFuture<'static, anyhow::Result<CopilotToken>> + Send + Sync>;
+
+pub struct CopilotProvider {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    token: Mutex<CopilotToken>,
+    refresh: TokenRefresher,
+    reauth_command: String,
+    request_timeout: Option<Duration>,
+}
+
+impl CopilotProvider {
+    pub fn new(
+        client: reqwest::Client,
+        model: impl Into<String>,
+        token: CopilotToken,
+        refresh: TokenRefresher,
+    ) -> Self {
+        Self {
+            client,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            model: model.into(),
+            token: Mutex::new(token),
+            refresh,
+            reauth_command: "tark auth copilot".to_string(),
+            request_timeout: None,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Bounds each HTTP request a non-streaming `chat` call sends (the
+    /// first attempt and, separately, its auth-retry), from
+    /// `config.llm.request_timeout_secs`; distinct from any stream idle
+    /// timeout. The token refresh is not bounded by it. `None` (the
+    /// default) leaves the requests unbounded.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    fn current_token(&self) -> String {
+        self.token.lock().unwrap().access_token.clone()
+    }
+
+    /// Refreshes the token if it's at or near expiry. Proactive, so a
+    /// normally-timed call never has to eat a failed request first.
+    async fn ensure_fresh(&self) -> Result<(), LlmError> {
+        let needs_refresh = self.token.lock().unwrap().needs_refresh();
+        if needs_refresh {
+            self.refresh_token().await?;
+        }
+        Ok(())
+    }
+
+    async fn refresh_token(&self) -> Result<(), LlmError> {
+        match (self.refresh)().await {
+            Ok(fresh) => {
+                *self.token.lock().unwrap() = fresh;
+                Ok(())
+            }
+            Err(_) => Err(LlmError::AuthExpired {
+                provider: "copilot".to_string(),
+                reauth_command: self.reauth_command.clone(),
+            }),
+        }
+    }
+
+    async fn send(&self, prompt: &str) -> anyhow::Result<reqwest::Response> {
+        let token = self.current_token();
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(token)
+            .json(&serde_json::json!({
+                "model": self.model,
+                "messages": [{ "role": "user", "content": prompt }],
+            }));
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        Ok(request
+            .send()
+            .await
+            .map_err(|e| crate::llm::error::from_send_error("copilot", e))?)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ChatCompletion {
+    choices: Vec<Choice>,
+}
+
+#[derive(serde::Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(serde::Deserialize)]
+struct ChoiceMessage {
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl LlmProvider for CopilotProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        self.ensure_fresh().await?;
+
+        let mut response = self.send(prompt).await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            self.refresh_token().await?;
+            response = self.send(prompt).await?;
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::llm::error::from_status("copilot", status, &body).into());
+        }
+
+        let body: ChatCompletion = response.json().await?;
+        let text = body
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .unwrap_or_default();
+        Ok(LlmResponse {
+            text,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,94 @@
+//! Up-front credential + connectivity checks for configured providers, so
+//! auth/availability problems surface before a session starts instead of
+//! as a confusing mid-chat error.
+
+use std::time::{Duration, Instant};
+
+/// How a provider authenticates, determining which check `check_provider`
+/// runs before the ping.
+#[derive(Debug, Clone)]
+pub enum AuthMethod {
+    /// Presence of an environment variable (e.g. `ANTHROPIC_API_KEY`).
+    EnvVar(String),
+    /// A plugin-backed auth provider, checked via its `auth_status`/
+    /// `provider_auth_status` export.
+    Plugin { plugin_name: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct ProviderCheckResult {
+    pub provider: String,
+    pub auth_ok: bool,
+    pub auth_detail: Option<String>,
+    /// `None` if auth failed and the ping was skipped.
+    pub ping_latency: Option<Duration>,
+    pub model_available: Option<bool>,
+    pub error: Option<String>,
+}
+
+impl ProviderCheckResult {
+    pub fn passed(&self) -> bool {
+        self.auth_ok && self.error.is_none() && self.model_available.unwrap_or(true)
+    }
+}
+
+/// Checks one provider's credentials, then (if auth succeeded) sends a
+/// tiny ping via `ping` and records its latency and reported model
+/// availability.
+pub async fn check_provider<P>(
+    provider_name: &str,
+    auth: &AuthMethod,
+    model_id: &str,
+    ping: P,
+) -> ProviderCheckResult
+where
+    P: std::future::Future<Output = anyhow::Result<bool>>,
+{
+    let (auth_ok, auth_detail) = match auth {
+        AuthMethod::EnvVar(name) => match std::env::var(name) {
+            Ok(_) => (true, None),
+            Err(_) => (false, Some(format!("environment variable `{name}` is not set"))),
+        },
+        AuthMethod::Plugin { plugin_name } => {
+            // Plugin auth providers expose `auth_status`/
+            // `provider_auth_status`; without live plugin wiring here we
+            // can only report which plugin would be asked.
+            (true, Some(format!("delegated to plugin `{plugin_name}` auth_status")))
+        }
+    };
+
+    if !auth_ok {
+        return ProviderCheckResult {
+            provider: provider_name.to_string(),
+            auth_ok,
+            auth_detail,
+            ping_latency: None,
+            model_available: None,
+            error: None,
+        };
+    }
+
+    let started_at = Instant::now();
+    match ping.await {
+        Ok(model_available) => ProviderCheckResult {
+            provider: provider_name.to_string(),
+            auth_ok,
+            auth_detail,
+            ping_latency: Some(started_at.elapsed()),
+            model_available: Some(model_available),
+            error: if model_available {
+                None
+            } else {
+                Some(format!("model `{model_id}` not reported as available"))
+            },
+        },
+        Err(err) => ProviderCheckResult {
+            provider: provider_name.to_string(),
+            auth_ok,
+            auth_detail,
+            ping_latency: Some(started_at.elapsed()),
+            model_available: None,
+            error: Some(err.to_string()),
+        },
+    }
+}
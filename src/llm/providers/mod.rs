@@ -0,0 +1,7 @@
+//! Concrete LLM provider implementations.
+
+pub mod copilot;
+pub mod health;
+pub mod ollama;
+pub mod openai_compat;
+pub mod openrouter;
@@ -0,0 +1,199 @@
+//! Local Ollama provider.
+
+use async_trait::async_trait;
+
+use crate::llm::{LlmProvider, LlmResponse, StreamChunk, TokenUsage};
+
+pub struct OllamaProvider {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    request_timeout: Option<std::time::Duration>,
+}
+
+impl OllamaProvider {
+    pub fn new(client: reqwest::Client, base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into(),
+            model: model.into(),
+            request_timeout: None,
+        }
+    }
+
+    /// Bounds how long a non-streaming `chat` call may take overall, from
+    /// `config.llm.request_timeout_secs`; distinct from any stream idle
+    /// timeout, and not applied to `chat_streaming` or the model-management
+    /// helpers below. `None` (the default) leaves the request unbounded.
+    pub fn with_request_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Whether the daemon at `base_url` is reachable at all.
+    pub async fn is_running(&self) -> bool {
+        self.client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await
+            .is_ok_and(|r| r.status().is_success())
+    }
+
+    /// Whether `self.model` is already pulled locally.
+    pub async fn is_model_present(&self) -> anyhow::Result<bool> {
+        #[derive(serde::Deserialize)]
+        struct Tags {
+            models: Vec<TagEntry>,
+        }
+        #[derive(serde::Deserialize)]
+        struct TagEntry {
+            name: String,
+        }
+
+        let tags: Tags = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await?
+            .json()
+            .await?;
+        Ok(tags.models.iter().any(|m| m.name == self.model))
+    }
+
+    /// Pull `self.model`, streaming progress lines and discarding them —
+    /// callers that want progress UI should use the raw `/api/pull`
+    /// endpoint directly instead.
+    pub async fn pull_model(&self) -> anyhow::Result<()> {
+        let response = self
+            .client
+            .post(format!("{}/api/pull", self.base_url))
+            .json(&serde_json::json!({ "name": self.model }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("failed to pull model `{}`: {}", self.model, response.status());
+        }
+        // Drain the streamed progress body so the connection completes.
+        let _ = response.bytes().await?;
+        Ok(())
+    }
+
+    /// Ensure the model is present, pulling it if necessary, before the
+    /// first chat request — Ollama otherwise errors with a cryptic 404.
+    pub async fn ensure_ready(&self) -> anyhow::Result<()> {
+        if !self.is_running().await {
+            anyhow::bail!("ollama daemon is not reachable at {}", self.base_url);
+        }
+        if !self.is_model_present().await? {
+            self.pull_model().await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct OllamaStreamLine {
+    response: String,
+    #[serde(default)]
+    done: bool,
+    /// Only present on the final (`done: true`) line.
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+impl OllamaStreamLine {
+    fn usage(&self) -> Option<TokenUsage> {
+        Some(TokenUsage {
+            input_tokens: self.prompt_eval_count?,
+            output_tokens: self.eval_count.unwrap_or(0),
+            estimated: false,
+        })
+    }
+}
+
+#[async_trait]
+impl LlmProvider for OllamaProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        let mut request = self
+            .client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&serde_json::json!({ "model": self.model, "prompt": prompt, "stream": false }));
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| crate::llm::error::from_send_error("ollama", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::llm::error::from_status("ollama", status, &body).into());
+        }
+        let body: OllamaStreamLine = response.json().await?;
+        let usage = body.usage();
+        Ok(LlmResponse {
+            text: body.response,
+            usage,
+            ..Default::default()
+        })
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn chat_streaming(
+        &self,
+        prompt: &str,
+        on_chunk: &mut (dyn FnMut(StreamChunk) + Send),
+    ) -> anyhow::Result<LlmResponse> {
+        use futures::StreamExt;
+
+        let response = self
+            .client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&serde_json::json!({ "model": self.model, "prompt": prompt, "stream": true }))
+            .send()
+            .await?;
+
+        let mut full = String::new();
+        let mut usage = None;
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk?));
+            while let Some(newline) = buffer.find('\n') {
+                let line = buffer[..newline].to_string();
+                buffer.drain(..=newline);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let parsed: OllamaStreamLine = serde_json::from_str(&line)?;
+                if !parsed.response.is_empty() {
+                    full.push_str(&parsed.response);
+                    on_chunk(StreamChunk {
+                        delta: parsed.response.clone(),
+                    });
+                }
+                if parsed.done {
+                    // Ollama only reports `prompt_eval_count`/`eval_count`
+                    // on this final line, so the plain `chat_streaming`
+                    // fallback here is exactly the path that used to drop
+                    // usage entirely for a streaming turn.
+                    usage = parsed.usage();
+                    break;
+                }
+            }
+        }
+
+        Ok(LlmResponse {
+            text: full,
+            usage,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,102 @@
+//! Generic provider for self-hosted servers that speak the OpenAI chat
+//! completions API (vLLM, LocalAI, LM Studio, and similar gateways), as
+//! opposed to a named hosted service like OpenRouter or Copilot. The
+//! request/response shape is the same plain `/chat/completions` call;
+//! what differs deployment to deployment is the base URL and, often, the
+//! TLS trust store — see `config.network.extra_ca_certs` and
+//! `danger_accept_invalid_certs` for pointing this at a gateway behind an
+//! internal CA.
+
+use async_trait::async_trait;
+
+use crate::llm::{LlmProvider, LlmResponse};
+
+pub struct OpenAiCompatProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    request_timeout: Option<std::time::Duration>,
+}
+
+impl OpenAiCompatProvider {
+    pub fn new(client: reqwest::Client, base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: base_url.into(),
+            api_key: None,
+            model: model.into(),
+            request_timeout: None,
+        }
+    }
+
+    /// Most self-hosted gateways don't require a key at all; set one only
+    /// if the deployment is configured to check for it.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Bounds how long a non-streaming `chat` call may take overall, from
+    /// `config.llm.request_timeout_secs`; distinct from any stream idle
+    /// timeout. `None` (the default) leaves the request unbounded.
+    pub fn with_request_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ChatCompletion {
+    choices: Vec<Choice>,
+}
+
+#[derive(serde::Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(serde::Deserialize)]
+struct ChoiceMessage {
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiCompatProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&serde_json::json!({
+                "model": self.model,
+                "messages": [{ "role": "user", "content": prompt }],
+            }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| crate::llm::error::from_send_error("openai_compat", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::llm::error::from_status("openai_compat", status, &body).into());
+        }
+        let body: ChatCompletion = response.json().await?;
+        let text = body
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .unwrap_or_default();
+        Ok(LlmResponse {
+            text,
+            ..Default::default()
+        })
+    }
+}
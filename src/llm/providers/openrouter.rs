@@ -0,0 +1,126 @@
+//! OpenRouter provider: a single chat completions endpoint that itself
+//! routes across many upstream model backends. Beyond the plain `model`
+//! selection every provider has, OpenRouter accepts a fallback `models`
+//! array and a `provider` routing-preferences object in the request body,
+//! and reports which upstream actually served the request back in the
+//! response — see <https://openrouter.ai/docs/features/model-routing>.
+
+use async_trait::async_trait;
+
+use crate::config::OpenRouterRouteConfig;
+use crate::llm::{LlmProvider, LlmResponse};
+
+const DEFAULT_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
+pub struct OpenRouterProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    route: OpenRouterRouteConfig,
+    request_timeout: Option<std::time::Duration>,
+}
+
+impl OpenRouterProvider {
+    pub fn new(client: reqwest::Client, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: api_key.into(),
+            model: model.into(),
+            route: OpenRouterRouteConfig::default(),
+            request_timeout: None,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Applies fallback-model and provider routing preferences from
+    /// `config.llm.openrouter.route`, carried in every subsequent request.
+    pub fn with_route(mut self, route: OpenRouterRouteConfig) -> Self {
+        self.route = route;
+        self
+    }
+
+    /// Bounds how long a non-streaming `chat` call may take overall, from
+    /// `config.llm.request_timeout_secs`; distinct from any stream idle
+    /// timeout. `None` (the default) leaves the request unbounded.
+    pub fn with_request_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    fn request_body(&self, prompt: &str) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+        if !self.route.models.is_empty() {
+            body["models"] = serde_json::json!(self.route.models);
+        }
+        if let Some(preferences) = &self.route.provider_preferences {
+            body["provider"] = preferences.clone();
+        }
+        body
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ChatCompletion {
+    choices: Vec<Choice>,
+    /// The upstream that actually served the request, present when
+    /// OpenRouter routed across more than one candidate. Named `provider`
+    /// in the response body rather than `model` since `model` still
+    /// echoes back the originally requested model id.
+    #[serde(default)]
+    provider: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(serde::Deserialize)]
+struct ChoiceMessage {
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl LlmProvider for OpenRouterProvider {
+    async fn chat(&self, prompt: &str) -> anyhow::Result<LlmResponse> {
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&self.request_body(prompt));
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| crate::llm::error::from_send_error("openrouter", e))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::llm::error::from_status("openrouter", status, &body).into());
+        }
+        let body: ChatCompletion = response.json().await?;
+        let text = body
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .unwrap_or_default();
+        Ok(LlmResponse {
+            text,
+            served_by: body.provider,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,65 @@
+//! Raw request/response transcript logging for debugging provider
+//! integrations, with secrets redacted before anything touches disk.
+
+use serde_json::Value;
+
+const SENSITIVE_KEYS: &[&str] = &[
+    "authorization",
+    "api_key",
+    "apikey",
+    "x-api-key",
+    "token",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "password",
+];
+
+/// Recursively redacts values under sensitive keys (case-insensitive) in a
+/// JSON document, replacing them with `"[REDACTED]"`.
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map.iter_mut() {
+                if SENSITIVE_KEYS.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                    *val = Value::String("[REDACTED]".to_string());
+                } else {
+                    redact(val);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Redact an `Authorization: Bearer ...`-style header value, keeping the
+/// scheme visible for debuggability.
+pub fn redact_header_value(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{scheme} [REDACTED]"),
+        None => "[REDACTED]".to_string(),
+    }
+}
+
+/// One logged request/response pair, ready to append to the raw log file.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RawLogEntry {
+    pub provider: String,
+    pub request: Value,
+    pub response: Value,
+}
+
+pub fn log_entry(provider: &str, mut request: Value, mut response: Value) -> RawLogEntry {
+    redact(&mut request);
+    redact(&mut response);
+    RawLogEntry {
+        provider: provider.to_string(),
+        request,
+        response,
+    }
+}
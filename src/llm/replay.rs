@@ -0,0 +1,54 @@
+//! Debug replay: feed a recorded `raw_log` transcript back to the agent
+//! as a scripted provider, so a reported bug can be reproduced offline
+//! without a live API key.
+
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+use crate::llm::raw_log::RawLogEntry;
+use crate::llm::{LlmProvider, LlmResponse};
+
+/// A provider that replays recorded responses in order instead of calling
+/// a real backend. Errors once the transcript is exhausted, since a
+/// replay run that makes more requests than were recorded indicates the
+/// agent's behavior diverged from the original run.
+pub struct ReplayProvider {
+    remaining: Mutex<std::collections::VecDeque<RawLogEntry>>,
+}
+
+impl ReplayProvider {
+    pub fn from_entries(entries: Vec<RawLogEntry>) -> Self {
+        Self {
+            remaining: Mutex::new(entries.into()),
+        }
+    }
+
+    pub fn from_ndjson(transcript: &str) -> anyhow::Result<Self> {
+        let entries = transcript
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<RawLogEntry>, _>>()?;
+        Ok(Self::from_entries(entries))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ReplayProvider {
+    async fn chat(&self, _prompt: &str) -> anyhow::Result<LlmResponse> {
+        let mut remaining = self.remaining.lock().unwrap();
+        let entry = remaining
+            .pop_front()
+            .ok_or_else(|| anyhow::anyhow!("replay transcript exhausted"))?;
+        let text = entry
+            .response
+            .get("text")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        Ok(LlmResponse {
+            text,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,65 @@
+//! Shared streaming abstractions used by every provider (see
+//! `docs/TOOL_CALL_ARCHITECTURE.md`).
+
+pub mod tool_tracker;
+
+use crate::llm::TokenUsage;
+
+/// Standard streaming event emitted by providers, independent of their
+/// wire format.
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    TextDelta(String),
+    ThinkingDelta(String),
+    ToolCallStart { id: String, name: String },
+    ToolCallDelta { id: String, args_delta: String },
+    ToolCallComplete { id: String },
+    /// Token usage for the turn, from the provider's own accounting or an
+    /// estimate; see [`crate::llm::LlmProvider::chat_streaming_with_thinking`].
+    Usage(TokenUsage),
+    Done,
+    Error(String),
+}
+
+/// Whether a provider should stop pulling further tokens once a tool call
+/// has fully streamed in. Most providers emit a final text turn after a
+/// tool call that the agent discards anyway (since the next turn requires
+/// the tool result), so stopping early saves a wasted generation once a
+/// provider's `finish_reason`/stop condition confirms the call is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopOnToolCall {
+    /// This provider reliably ends the stream itself once a tool call
+    /// completes; no extra action needed.
+    ProviderStops,
+    /// This provider keeps streaming after a tool call completes, so the
+    /// consumer must close the connection once it sees
+    /// `StreamEvent::ToolCallComplete` for every call in the turn.
+    ClientMustStop,
+}
+
+/// Tracks in-flight tool calls for a turn so callers can tell when every
+/// announced call has completed and, for providers that need it, close
+/// the stream early.
+#[derive(Debug, Default)]
+pub struct ToolCallCompletionGate {
+    started: Vec<String>,
+    completed: Vec<String>,
+}
+
+impl ToolCallCompletionGate {
+    pub fn observe(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::ToolCallStart { id, .. } => self.started.push(id.clone()),
+            StreamEvent::ToolCallComplete { id } => self.completed.push(id.clone()),
+            _ => {}
+        }
+    }
+
+    /// True once every tool call started in this turn has also completed
+    /// and at least one was seen, i.e. it's safe to stop pulling tokens.
+    pub fn should_stop(&self, policy: StopOnToolCall) -> bool {
+        policy == StopOnToolCall::ClientMustStop
+            && !self.started.is_empty()
+            && self.started.len() == self.completed.len()
+    }
+}
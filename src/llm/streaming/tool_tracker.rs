@@ -0,0 +1,52 @@
+//! Maps provider-specific tool call identifiers to canonical call IDs and
+//! accumulates streamed argument fragments.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+struct PendingCall {
+    canonical_id: String,
+    name: String,
+    args: String,
+}
+
+/// Tracks tool call state across a single streaming turn. Used by
+/// providers whose wire format announces a call and then streams its
+/// arguments separately (OpenAI Responses API, Claude).
+#[derive(Debug, Default)]
+pub struct ToolCallTracker {
+    by_provider_id: HashMap<String, PendingCall>,
+}
+
+impl ToolCallTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&mut self, provider_id: String, canonical_id: String, name: String) {
+        self.by_provider_id.insert(
+            provider_id,
+            PendingCall {
+                canonical_id,
+                name,
+                args: String::new(),
+            },
+        );
+    }
+
+    pub fn append_args(&mut self, provider_id: &str, delta: &str) {
+        if let Some(call) = self.by_provider_id.get_mut(provider_id) {
+            call.args.push_str(delta);
+        }
+    }
+
+    pub fn canonical_id(&self, provider_id: &str) -> Option<&str> {
+        self.by_provider_id.get(provider_id).map(|c| c.canonical_id.as_str())
+    }
+
+    pub fn finish(&mut self, provider_id: &str) -> Option<(String, String, String)> {
+        self.by_provider_id
+            .remove(provider_id)
+            .map(|c| (c.canonical_id, c.name, c.args))
+    }
+}
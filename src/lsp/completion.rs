@@ -0,0 +1,41 @@
+//! `textDocument/completion` request handling: debouncing and
+//! de-duplication so rapid keystrokes don't each trigger a model call.
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompletionKey {
+    pub uri: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Suppresses completion requests that arrive within `debounce` of the
+/// previous request for the same position. It only decides whether a new
+/// request is sent; it does not track or cancel requests already sent.
+pub struct CompletionDebouncer {
+    debounce: Duration,
+    last_request: Option<(CompletionKey, Instant)>,
+}
+
+impl CompletionDebouncer {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            last_request: None,
+        }
+    }
+
+    /// Call on every incoming request. Returns `true` if the request
+    /// should actually be sent to the model, `false` if it should be
+    /// suppressed as a duplicate/too-soon repeat.
+    pub fn should_request(&mut self, key: CompletionKey) -> bool {
+        let now = Instant::now();
+        let should_send = match &self.last_request {
+            Some((last_key, last_at)) => *last_key != key || last_at.elapsed() >= self.debounce,
+            None => true,
+        };
+        self.last_request = Some((key, now));
+        should_send
+    }
+}
@@ -0,0 +1,126 @@
+//! Diagnostic publishing: severity levels and the filtering applied before
+//! diagnostics are sent to the editor adapter.
+
+use std::collections::HashMap;
+
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Hint,
+    Information,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: String,
+    pub line: u32,
+    pub col: u32,
+    pub severity: Severity,
+    pub message: String,
+    /// Free-form source of the diagnostic (e.g. `"lint"`, `"type-check"`,
+    /// `"review"`). `None` means uncategorized and is never filtered out
+    /// by a category allowlist.
+    pub category: Option<String>,
+}
+
+/// Drops diagnostics below `min_severity` or outside `allowed_categories`
+/// (when set), then caps the number kept per file at `max_per_file`,
+/// preferring the most severe ones when a file exceeds the cap.
+///
+/// `allowed_categories` of `None` disables category filtering entirely;
+/// `Some(&[])` would drop every categorized diagnostic, which is an odd
+/// but valid way to ask for uncategorized diagnostics only.
+pub fn filter_diagnostics(
+    mut diagnostics: Vec<Diagnostic>,
+    min_severity: Severity,
+    max_per_file: usize,
+    allowed_categories: Option<&[String]>,
+) -> Vec<Diagnostic> {
+    diagnostics.retain(|d| d.severity >= min_severity);
+    if let Some(allowed) = allowed_categories {
+        diagnostics.retain(|d| match &d.category {
+            Some(category) => allowed.iter().any(|a| a == category),
+            None => true,
+        });
+    }
+
+    diagnostics.sort_by(|a, b| a.path.cmp(&b.path).then(b.severity.cmp(&a.severity)));
+
+    let mut result = Vec::with_capacity(diagnostics.len());
+    let mut current_path: Option<&str> = None;
+    let mut count_in_path = 0usize;
+    for d in &diagnostics {
+        if current_path != Some(d.path.as_str()) {
+            current_path = Some(d.path.as_str());
+            count_in_path = 0;
+        }
+        if count_in_path < max_per_file {
+            result.push(d.clone());
+            count_in_path += 1;
+        }
+    }
+    result
+}
+
+/// Ensures only the diagnostics from the most recent save of a document are
+/// ever published: starting a new publish cancels whatever publish for the
+/// same URI was still in flight.
+///
+/// Mirrors the cancellation idiom used by `http::RequestCancellationRegistry`
+/// rather than `InlayHintCache`'s version stamp, since diagnostics are
+/// produced by a cancellable async computation (e.g. an LLM review pass)
+/// that should stop doing work once superseded, not just have its result
+/// discarded.
+#[derive(Debug, Default)]
+pub struct DiagnosticsPublisher {
+    in_flight: HashMap<String, (u64, CancellationToken)>,
+    next_generation: u64,
+}
+
+/// Handle returned by [`DiagnosticsPublisher::begin_publish`]. Holding it
+/// lets the caller check `token.is_cancelled()` mid-computation and, once
+/// done, call [`DiagnosticsPublisher::finish_publish`] to release the slot.
+#[derive(Debug, Clone)]
+pub struct PublishHandle {
+    generation: u64,
+    pub token: CancellationToken,
+}
+
+impl DiagnosticsPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels any publish already in flight for `uri` and returns a handle
+    /// the caller should watch to know whether its own publish has since
+    /// been superseded.
+    pub fn begin_publish(&mut self, uri: &str) -> PublishHandle {
+        if let Some((_, previous)) = self.in_flight.remove(uri) {
+            previous.cancel();
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let token = CancellationToken::new();
+        self.in_flight
+            .insert(uri.to_string(), (generation, token.clone()));
+        PublishHandle { generation, token }
+    }
+
+    /// Clears the tracked publish for `uri` once it completes, but only if
+    /// `handle` is still the current one for that URI — a superseded
+    /// publish must not clobber the entry owned by the publish that
+    /// replaced it.
+    pub fn finish_publish(&mut self, uri: &str, handle: &PublishHandle) {
+        if self
+            .in_flight
+            .get(uri)
+            .is_some_and(|(generation, _)| *generation == handle.generation)
+        {
+            self.in_flight.remove(uri);
+        }
+    }
+}
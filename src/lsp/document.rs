@@ -0,0 +1,89 @@
+//! Incremental document synchronization: applies `textDocument/didChange`
+//! range edits to a cached buffer instead of re-reading the whole file on
+//! every keystroke.
+
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentChange {
+    /// `None` means a full-document replacement.
+    pub range: Option<Range>,
+    pub text: String,
+}
+
+/// A document kept in sync incrementally, tracked by version so stale
+/// responses (e.g. diagnostics computed against an older version) can be
+/// discarded.
+pub struct SyncedDocument {
+    pub uri: String,
+    pub version: i64,
+    lines: Vec<String>,
+}
+
+impl SyncedDocument {
+    pub fn new(uri: String, version: i64, text: String) -> Self {
+        Self {
+            uri,
+            version,
+            lines: split_lines(&text),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Apply a batch of changes in order, bumping the document version.
+    /// Changes without a range replace the whole document.
+    pub fn apply_changes(&mut self, new_version: i64, changes: Vec<ContentChange>) {
+        for change in changes {
+            match change.range {
+                None => self.lines = split_lines(&change.text),
+                Some(range) => self.apply_range_edit(range, &change.text),
+            }
+        }
+        self.version = new_version;
+    }
+
+    fn apply_range_edit(&mut self, range: Range, text: &str) {
+        let start_line = range.start.line as usize;
+        let end_line = range.end.line as usize;
+
+        let prefix = self
+            .lines
+            .get(start_line)
+            .map(|l| l.chars().take(range.start.character as usize).collect::<String>())
+            .unwrap_or_default();
+        let suffix = self
+            .lines
+            .get(end_line)
+            .map(|l| l.chars().skip(range.end.character as usize).collect::<String>())
+            .unwrap_or_default();
+
+        let mut replacement = split_lines(&format!("{prefix}{text}{suffix}"));
+        if replacement.is_empty() {
+            replacement.push(String::new());
+        }
+
+        let end = (end_line + 1).min(self.lines.len());
+        self.lines.splice(start_line.min(self.lines.len())..end, replacement);
+    }
+}
+
+fn split_lines(text: &str) -> Vec<String> {
+    if text.is_empty() {
+        vec![String::new()]
+    } else {
+        text.split('\n').map(str::to_string).collect()
+    }
+}
@@ -0,0 +1,24 @@
+//! `textDocument/hover`: combines a symbol's static context (from
+//! `workspace_symbols`) with an LLM-generated explanation.
+
+use crate::lsp::workspace_symbols::WorkspaceSymbol;
+
+#[derive(Debug, Clone)]
+pub struct HoverContent {
+    pub markdown: String,
+}
+
+/// Build the hover payload for `symbol`, prefixing the model's
+/// explanation with the symbol's kind/location so the response is useful
+/// even if the explanation is terse.
+pub fn build_hover(symbol: &WorkspaceSymbol, llm_explanation: &str) -> HoverContent {
+    let markdown = format!(
+        "**{name}** _{kind}_ — `{path}:{line}`\n\n{explanation}",
+        name = symbol.name,
+        kind = symbol.kind,
+        path = symbol.path,
+        line = symbol.line,
+        explanation = llm_explanation.trim(),
+    );
+    HoverContent { markdown }
+}
@@ -0,0 +1,73 @@
+//! `textDocument/inlayHint` support.
+//!
+//! Hints are produced by asking the model to annotate parameter names at
+//! call sites and inferred types for visible bindings, then cached per
+//! document version so re-opening the same viewport doesn't re-query the
+//! model.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// One inlay hint, matching the shape the editor adapter expects (see
+/// `docs/EDITOR_ADAPTER_API.md`).
+#[derive(Debug, Clone)]
+pub struct InlayHint {
+    pub line: u32,
+    pub col: u32,
+    pub label: String,
+    pub kind: InlayHintKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlayHintKind {
+    Type,
+    Parameter,
+}
+
+struct CacheEntry {
+    version: i64,
+    hints: Vec<InlayHint>,
+    computed_at: Instant,
+}
+
+/// Per-document inlay hint cache, with request debouncing so rapid
+/// viewport scrolling doesn't re-query the model on every frame.
+pub struct InlayHintCache {
+    entries: HashMap<String, CacheEntry>,
+    debounce: Duration,
+}
+
+impl InlayHintCache {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            debounce,
+        }
+    }
+
+    /// Returns cached hints for `uri` if they were computed for `version`.
+    /// The debounce window plays no part here; see [`Self::should_debounce`].
+    pub fn get(&self, uri: &str, version: i64) -> Option<&[InlayHint]> {
+        let entry = self.entries.get(uri)?;
+        (entry.version == version).then_some(entry.hints.as_slice())
+    }
+
+    /// Whether a fresh request for `uri` should be debounced (i.e.
+    /// suppressed because the last computation is too recent).
+    pub fn should_debounce(&self, uri: &str) -> bool {
+        self.entries
+            .get(uri)
+            .is_some_and(|e| e.computed_at.elapsed() < self.debounce)
+    }
+
+    pub fn store(&mut self, uri: String, version: i64, hints: Vec<InlayHint>) {
+        self.entries.insert(
+            uri,
+            CacheEntry {
+                version,
+                hints,
+                computed_at: Instant::now(),
+            },
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! LSP server implementation: completions, diagnostics, code actions, and
+//! related editor-facing features.
+
+pub mod completion;
+pub mod diagnostics;
+pub mod document;
+pub mod hover;
+pub mod inlay_hints;
+pub mod quickfix;
+pub mod refactor_action;
+pub mod review_diagnostics;
+pub mod workspace_symbols;
@@ -0,0 +1,37 @@
+//! Confidence-gated quickfix actions.
+//!
+//! Quickfixes are edits the model proposes in response to a diagnostic.
+//! Each fix carries a self-rated confidence score so that low-confidence
+//! suggestions can be downgraded to a preview instead of being applied
+//! straight to the buffer.
+
+/// A single quickfix suggestion returned by the model.
+#[derive(Debug, Clone)]
+pub struct QuickfixSuggestion {
+    pub title: String,
+    pub edit: String,
+    /// Model self-rating in `[0.0, 1.0]`, derived from an explicit
+    /// self-assessment or, where the provider exposes them, token logprobs.
+    pub confidence: f32,
+}
+
+/// What the LSP server should do with a [`QuickfixSuggestion`] once
+/// resolved against the configured threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickfixDisposition {
+    /// Confidence met the threshold; apply the edit directly.
+    AutoApply,
+    /// Confidence was below the threshold; show the edit but require the
+    /// user to confirm before it is applied.
+    PreviewOnly,
+}
+
+/// Decide how a quickfix should be surfaced given the configured minimum
+/// confidence threshold.
+pub fn classify(suggestion: &QuickfixSuggestion, min_confidence: f32) -> QuickfixDisposition {
+    if suggestion.confidence >= min_confidence {
+        QuickfixDisposition::AutoApply
+    } else {
+        QuickfixDisposition::PreviewOnly
+    }
+}
@@ -0,0 +1,48 @@
+//! Code action that turns a `suggest_refactorings` tool result into an
+//! applicable `textDocument/codeAction` response.
+
+use crate::lsp::quickfix::{classify, QuickfixDisposition, QuickfixSuggestion};
+
+/// One refactoring suggestion as returned by the `suggest_refactorings`
+/// tool, before it's been turned into an LSP code action.
+#[derive(Debug, Clone)]
+pub struct RefactoringSuggestion {
+    pub title: String,
+    pub rationale: String,
+    pub edit: String,
+    pub confidence: f32,
+}
+
+/// An LSP `CodeAction`-shaped refactor, ready to hand to the editor
+/// adapter. `preview_only` mirrors quickfix's confidence gate so a
+/// low-confidence refactor still shows up but requires confirmation.
+#[derive(Debug, Clone)]
+pub struct RefactorCodeAction {
+    pub title: String,
+    pub edit: String,
+    pub preview_only: bool,
+}
+
+pub fn to_code_actions(
+    suggestions: Vec<RefactoringSuggestion>,
+    min_confidence: f32,
+) -> Vec<RefactorCodeAction> {
+    suggestions
+        .into_iter()
+        .map(|s| {
+            let disposition = classify(
+                &QuickfixSuggestion {
+                    title: s.title.clone(),
+                    edit: s.edit.clone(),
+                    confidence: s.confidence,
+                },
+                min_confidence,
+            );
+            RefactorCodeAction {
+                title: s.title,
+                edit: s.edit,
+                preview_only: disposition == QuickfixDisposition::PreviewOnly,
+            }
+        })
+        .collect()
+}
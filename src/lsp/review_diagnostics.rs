@@ -0,0 +1,42 @@
+//! Turns `review_code` tool findings into published diagnostics with
+//! attached quickfixes, run on document save.
+
+use crate::lsp::diagnostics::{Diagnostic, Severity};
+use crate::lsp::quickfix::QuickfixSuggestion;
+
+/// One finding from the `review_code` tool, before it's split into a
+/// diagnostic and (optionally) a quickfix.
+#[derive(Debug, Clone)]
+pub struct ReviewFinding {
+    pub line: u32,
+    pub col: u32,
+    pub severity: Severity,
+    pub message: String,
+    pub suggested_fix: Option<QuickfixSuggestion>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticWithFix {
+    pub diagnostic: Diagnostic,
+    pub fix: Option<QuickfixSuggestion>,
+}
+
+/// Run on save: convert review findings for `path` into diagnostics the
+/// editor can render, each carrying its quickfix (if any) for the code
+/// action provider to pick up.
+pub fn findings_to_diagnostics(path: &str, findings: Vec<ReviewFinding>) -> Vec<DiagnosticWithFix> {
+    findings
+        .into_iter()
+        .map(|f| DiagnosticWithFix {
+            diagnostic: Diagnostic {
+                path: path.to_string(),
+                line: f.line,
+                col: f.col,
+                severity: f.severity,
+                message: f.message,
+                category: Some("review".to_string()),
+            },
+            fix: f.suggested_fix,
+        })
+        .collect()
+}
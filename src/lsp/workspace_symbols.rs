@@ -0,0 +1,76 @@
+//! `workspace/symbol` support.
+//!
+//! Symbols are gathered from `ctags` when available (fast, exact) and
+//! merged with model-derived symbols for files ctags doesn't understand.
+//! Ctags results always take precedence on name collisions since they're
+//! derived from the actual syntax rather than a guess.
+
+use std::process::Command;
+
+#[derive(Debug, Clone)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub line: u32,
+}
+
+/// Run `ctags` over the workspace and parse its tab-separated output.
+/// Returns an empty list (rather than erroring) when `ctags` isn't on
+/// `PATH`, so callers can fall back to the LLM-only path.
+pub fn ctags_symbols(workspace_root: &str) -> Vec<WorkspaceSymbol> {
+    let output = Command::new("ctags")
+        .args(["-R", "-x", "--_xformat=%N\t%K\t%F\t%n"])
+        .current_dir(workspace_root)
+        .output();
+
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ctags_line)
+        .collect()
+}
+
+fn parse_ctags_line(line: &str) -> Option<WorkspaceSymbol> {
+    let mut fields = line.split('\t');
+    let name = fields.next()?.to_string();
+    let kind = fields.next()?.to_string();
+    let path = fields.next()?.to_string();
+    let line_no = fields.next()?.parse().ok()?;
+    Some(WorkspaceSymbol {
+        name,
+        kind,
+        path,
+        line: line_no,
+    })
+}
+
+/// Merge ctags and model-derived symbols, filtering both by a fuzzy
+/// substring match on `query`, with ctags entries first.
+pub fn search_symbols(
+    query: &str,
+    ctags: Vec<WorkspaceSymbol>,
+    llm_derived: Vec<WorkspaceSymbol>,
+) -> Vec<WorkspaceSymbol> {
+    let query = query.to_lowercase();
+    let matches = |s: &WorkspaceSymbol| s.name.to_lowercase().contains(&query);
+
+    let mut seen: std::collections::HashSet<(String, String)> = std::collections::HashSet::new();
+    let mut results = Vec::new();
+    for symbol in ctags.into_iter().filter(matches) {
+        seen.insert((symbol.name.clone(), symbol.path.clone()));
+        results.push(symbol);
+    }
+    for symbol in llm_derived.into_iter().filter(matches) {
+        if seen.insert((symbol.name.clone(), symbol.path.clone())) {
+            results.push(symbol);
+        }
+    }
+    results
+}
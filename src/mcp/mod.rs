@@ -0,0 +1,76 @@
+//! MCP (Model Context Protocol) client: connects to configured MCP
+//! servers and surfaces their tools through the same `ToolDefinition`
+//! shape native tools use.
+
+use serde::{Deserialize, Serialize};
+
+pub mod transport;
+
+use transport::McpTransport;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    pub name: String,
+    /// `stdio` spawns `command`; `sse`/`http` connect to `url` (see
+    /// `transport`).
+    pub transport: McpTransportKind,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransportKind {
+    Stdio,
+    Sse,
+    Http,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpToolDescriptor {
+    pub name: String,
+    pub description: String,
+    pub input_schema: serde_json::Value,
+}
+
+/// A connected MCP server, exposing its advertised tools and letting the
+/// agent invoke them.
+pub struct McpClient {
+    server_name: String,
+    transport: Box<dyn McpTransport>,
+}
+
+impl McpClient {
+    pub fn new(server_name: String, transport: Box<dyn McpTransport>) -> Self {
+        Self {
+            server_name,
+            transport,
+        }
+    }
+
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    pub async fn list_tools(&mut self) -> anyhow::Result<Vec<McpToolDescriptor>> {
+        let response = self.transport.request("tools/list", serde_json::json!({})).await?;
+        let tools = response
+            .get("tools")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("MCP server `{}` returned no `tools` field", self.server_name))?;
+        Ok(serde_json::from_value(tools)?)
+    }
+
+    pub async fn call_tool(&mut self, name: &str, args: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        self.transport
+            .request(
+                "tools/call",
+                serde_json::json!({ "name": name, "arguments": args }),
+            )
+            .await
+    }
+}
@@ -0,0 +1,155 @@
+//! Transports used to talk to MCP servers. `Stdio` spawns the server as a
+//! child process and speaks JSON-RPC over its stdin/stdout.
+
+use async_trait::async_trait;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin};
+
+/// A JSON-RPC 2.0 transport to an MCP server.
+#[async_trait]
+pub trait McpTransport: Send {
+    async fn request(&mut self, method: &str, params: serde_json::Value) -> anyhow::Result<serde_json::Value>;
+}
+
+/// Spawns the server as a subprocess and exchanges newline-delimited
+/// JSON-RPC messages over stdin/stdout.
+pub struct StdioTransport {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<tokio::process::ChildStdout>,
+    next_id: u64,
+}
+
+impl StdioTransport {
+    pub fn spawn(command: &str, args: &[String]) -> anyhow::Result<Self> {
+        let mut child = tokio::process::Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("no stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow::anyhow!("no stdout"))?;
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 1,
+        })
+    }
+}
+
+#[async_trait]
+impl McpTransport for StdioTransport {
+    async fn request(&mut self, method: &str, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        let mut response_line = String::new();
+        self.stdout.read_line(&mut response_line).await?;
+        let response: serde_json::Value = serde_json::from_str(&response_line)?;
+
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("MCP error from `{method}`: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("MCP response to `{method}` missing `result`"))
+    }
+}
+
+impl Drop for StdioTransport {
+    fn drop(&mut self) {
+        let _ = self.child.start_kill();
+    }
+}
+
+/// Talks to a remote MCP server over HTTP, using a single POST per
+/// request and reading the response either as a plain JSON body or, when
+/// the server replies with `text/event-stream`, as an SSE stream whose
+/// final `data:` event carries the JSON-RPC response.
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    next_id: u64,
+}
+
+impl HttpTransport {
+    pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            next_id: 1,
+        }
+    }
+}
+
+#[async_trait]
+impl McpTransport for HttpTransport {
+    async fn request(&mut self, method: &str, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&body)
+            .send()
+            .await?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        let value: serde_json::Value = if content_type.starts_with("text/event-stream") {
+            let text = response.text().await?;
+            parse_sse_final_json(&text)?
+        } else {
+            response.json().await?
+        };
+
+        if let Some(error) = value.get("error") {
+            anyhow::bail!("MCP error from `{method}`: {error}");
+        }
+        value
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("MCP response to `{method}` missing `result`"))
+    }
+}
+
+/// Extract the JSON payload from the last `data:` line of an SSE body.
+fn parse_sse_final_json(body: &str) -> anyhow::Result<serde_json::Value> {
+    let last_data = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .next_back()
+        .ok_or_else(|| anyhow::anyhow!("SSE response contained no `data:` events"))?;
+    Ok(serde_json::from_str(last_data.trim())?)
+}
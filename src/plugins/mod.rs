@@ -0,0 +1,91 @@
+//! WASM plugin host. Loads plugin modules described in `plugin.toml` and
+//! exposes their exports to the rest of `tark` (see `docs/PLUGIN_SDK.md`).
+
+use std::sync::OnceLock;
+
+use crate::config::NetworkConfig;
+use crate::core::{proxy, tls};
+
+static SHARED_BLOCKING_CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+
+/// The shared blocking HTTP client for plugin host calls (channel plugins
+/// invoked synchronously from `with_channel_instance`, auth plugins, ...),
+/// built once (applying `network`'s proxy settings) and reused instead of
+/// constructing a fresh client per call. See
+/// [`crate::llm::client::shared_client`] for the async equivalent used by
+/// the built-in LLM providers. Only the first caller's `network` takes
+/// effect, since the client is a process-wide singleton.
+///
+/// Proxying happens below this client, so it doesn't bypass a plugin's own
+/// domain allowlist check (see `crate::tools::web_fetch::domain_allowed`):
+/// that check runs against the plugin's actual request URL, not whatever
+/// host the proxy ultimately forwards to.
+pub fn shared_blocking_client(network: &NetworkConfig) -> reqwest::blocking::Client {
+    SHARED_BLOCKING_CLIENT.get_or_init(|| build_blocking_client(network)).clone()
+}
+
+fn build_blocking_client(network: &NetworkConfig) -> reqwest::blocking::Client {
+    let mut builder = reqwest::blocking::Client::builder();
+    let no_proxy = proxy::resolve(network.no_proxy.as_deref(), "NO_PROXY");
+
+    if let Some(url) = proxy::resolve(network.http_proxy.as_deref(), "HTTP_PROXY") {
+        if let Ok(mut http_proxy) = reqwest::Proxy::http(&url) {
+            http_proxy = http_proxy.no_proxy(no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            builder = builder.proxy(http_proxy);
+        }
+    }
+    if let Some(url) = proxy::resolve(network.https_proxy.as_deref(), "HTTPS_PROXY") {
+        if let Ok(mut https_proxy) = reqwest::Proxy::https(&url) {
+            https_proxy = https_proxy.no_proxy(no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            builder = builder.proxy(https_proxy);
+        }
+    }
+
+    builder = tls::apply(builder, network);
+
+    builder.build().unwrap_or_else(|_| reqwest::blocking::Client::new())
+}
+
+/// A loaded, instantiated plugin module.
+pub struct PluginInstance {
+    // Wasmtime `Instance`/`Store` handles would live here; omitted since
+    // this crate is not wired up to a wasm runtime in this tree.
+    name: String,
+    exported_functions: Vec<String>,
+}
+
+impl PluginInstance {
+    pub fn new(name: impl Into<String>, exported_functions: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            exported_functions,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn exports_function(&self, name: &str) -> bool {
+        self.exported_functions.iter().any(|f| f == name)
+    }
+
+    /// Call the plugin's buffered `provider_chat` export.
+    pub fn call_provider_chat(&self, _prompt: &str) -> anyhow::Result<String> {
+        anyhow::bail!("plugin `{}` does not export provider_chat", self.name)
+    }
+
+    /// Call the plugin's `provider_chat_stream` export, registering `on_chunk`
+    /// as the host-side handler for the `tark:stream` host function the
+    /// plugin writes chunks into while it runs.
+    pub fn call_provider_chat_stream(
+        &self,
+        _prompt: &str,
+        _on_chunk: &mut dyn FnMut(&str),
+    ) -> anyhow::Result<()> {
+        anyhow::bail!(
+            "plugin `{}` does not export provider_chat_stream",
+            self.name
+        )
+    }
+}
@@ -0,0 +1,4 @@
+//! System-prompt assembly: template rendering and rule composition.
+
+pub mod pipeline;
+pub mod template;
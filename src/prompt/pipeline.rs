@@ -0,0 +1,70 @@
+//! System-prompt assembly pipeline: an ordered set of stages, each
+//! contributing (or rewriting) a section of the final prompt.
+
+/// One contribution to the assembled system prompt. `order` controls
+/// placement — lower sorts earlier — and ties break on registration order
+/// so plugin-contributed rules land deterministically relative to the
+/// built-ins that registered them.
+#[derive(Debug, Clone)]
+pub struct PromptSection {
+    pub name: String,
+    pub order: i32,
+    pub content: String,
+}
+
+pub trait PromptStage: Send + Sync {
+    /// Produce this stage's section(s) given the sections assembled by
+    /// earlier stages so far, letting e.g. a "rules" stage reference what
+    /// the "persona" stage already emitted.
+    fn contribute(&self, so_far: &[PromptSection]) -> Vec<PromptSection>;
+}
+
+#[derive(Default)]
+pub struct PromptPipeline {
+    stages: Vec<Box<dyn PromptStage>>,
+}
+
+impl PromptPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_stage(&mut self, stage: Box<dyn PromptStage>) {
+        self.stages.push(stage);
+    }
+
+    /// Run every stage in registration order, then sort the combined
+    /// sections by `order` (stable, so registration order remains the
+    /// tiebreaker) and join their content.
+    pub fn assemble(&self) -> String {
+        let mut sections: Vec<PromptSection> = Vec::new();
+        for stage in &self.stages {
+            let mut contributed = stage.contribute(&sections);
+            sections.append(&mut contributed);
+        }
+        sections.sort_by_key(|s| s.order);
+        sections
+            .into_iter()
+            .map(|s| s.content)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// A stage that always contributes the same fixed section, useful for
+/// static persona/rule text registered by config or a plugin.
+pub struct StaticSection {
+    pub name: String,
+    pub order: i32,
+    pub content: String,
+}
+
+impl PromptStage for StaticSection {
+    fn contribute(&self, _so_far: &[PromptSection]) -> Vec<PromptSection> {
+        vec![PromptSection {
+            name: self.name.clone(),
+            order: self.order,
+            content: self.content.clone(),
+        }]
+    }
+}
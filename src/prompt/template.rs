@@ -0,0 +1,108 @@
+//! Minimal prompt-template engine: variable interpolation, `{{> name}}`
+//! includes, and `{{#if var}} ... {{/if}}` conditionals. Intentionally
+//! small in scope — this isn't a general templating language, just enough
+//! structure for composing system prompts and rule fragments.
+
+use std::collections::HashMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("unknown include `{0}`")]
+    UnknownInclude(String),
+    #[error("unterminated `{{{{#if {0}}}}}`")]
+    UnterminatedIf(String),
+    #[error("`{{{{/if}}}}` with no matching `{{{{#if}}}}`")]
+    DanglingEndIf,
+}
+
+/// Named template fragments available to `{{> name}}` includes.
+pub struct TemplateRegistry {
+    fragments: HashMap<String, String>,
+}
+
+impl TemplateRegistry {
+    pub fn new() -> Self {
+        Self {
+            fragments: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, source: impl Into<String>) {
+        self.fragments.insert(name.into(), source.into());
+    }
+
+    /// Render `source` against `vars`, resolving includes and
+    /// conditionals. Includes are expanded before conditionals/variables
+    /// are evaluated in the included text, so a fragment can itself use
+    /// `{{var}}` / `{{#if}}`.
+    pub fn render(&self, source: &str, vars: &HashMap<String, String>) -> Result<String, TemplateError> {
+        let expanded = self.expand_includes(source)?;
+        let conditioned = eval_conditionals(&expanded, vars)?;
+        Ok(interpolate(&conditioned, vars))
+    }
+
+    fn expand_includes(&self, source: &str) -> Result<String, TemplateError> {
+        let mut out = String::with_capacity(source.len());
+        let mut rest = source;
+        while let Some(start) = rest.find("{{>") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 3..];
+            let end = after.find("}}").ok_or(TemplateError::UnknownInclude(after.to_string()))?;
+            let name = after[..end].trim();
+            let fragment = self
+                .fragments
+                .get(name)
+                .ok_or_else(|| TemplateError::UnknownInclude(name.to_string()))?;
+            out.push_str(&self.expand_includes(fragment)?);
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+impl Default for TemplateRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Strips `{{#if var}} ... {{/if}}` blocks whose `var` is absent or empty
+/// in `vars`, keeping the inner content otherwise. Nested blocks are not
+/// supported: each `{{#if}}` is paired with the first `{{/if}}` after it,
+/// so a nested block leaves a stray `{{/if}}` and fails with
+/// [`TemplateError::DanglingEndIf`].
+fn eval_conditionals(source: &str, vars: &HashMap<String, String>) -> Result<String, TemplateError> {
+    let mut text = source.to_string();
+    while let Some(start) = text.find("{{#if ") {
+        let header_end = text[start..]
+            .find("}}")
+            .map(|i| start + i + 2)
+            .ok_or_else(|| TemplateError::UnterminatedIf(text[start..].to_string()))?;
+        let var_name = text[start + 6..header_end - 2].trim().to_string();
+
+        let close_tag = "{{/if}}";
+        let close_pos = text[header_end..]
+            .find(close_tag)
+            .map(|i| header_end + i)
+            .ok_or_else(|| TemplateError::UnterminatedIf(var_name.clone()))?;
+
+        let body = text[header_end..close_pos].to_string();
+        let truthy = vars.get(&var_name).is_some_and(|v| !v.is_empty());
+        let replacement = if truthy { body } else { String::new() };
+
+        text.replace_range(start..close_pos + close_tag.len(), &replacement);
+    }
+    if text.contains("{{/if}}") {
+        return Err(TemplateError::DanglingEndIf);
+    }
+    Ok(text)
+}
+
+fn interpolate(source: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = source.to_string();
+    for (key, value) in vars {
+        out = out.replace(&format!("{{{{{key}}}}}"), value);
+    }
+    out
+}
@@ -0,0 +1,181 @@
+//! Structured question/answer exchanges conducted over a channel or the
+//! CLI: a question is answered into an [`AnswerValue`], validated against
+//! its type's constraints, and re-prompted with the specific problem
+//! (rather than a generic "invalid answer") up to a configurable retry
+//! count — see [`crate::config::RemoteConfig::questionnaire_max_retries`].
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuestionType {
+    SingleSelect { options: Vec<String> },
+    MultiSelect { options: Vec<String> },
+    FreeText,
+    /// A yes/no answer, accepting common spellings ("y", "yes", "true",
+    /// "n", "no", "false", case-insensitive).
+    Confirm,
+    /// A numeric answer, optionally bounded by `min`/`max` (inclusive).
+    Number {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+    },
+    /// An ISO-8601 calendar date (`YYYY-MM-DD`).
+    Date,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    pub id: String,
+    pub prompt: String,
+    #[serde(flatten)]
+    pub kind: QuestionType,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerValue {
+    Text(String),
+    Choice(String),
+    Choices(Vec<String>),
+    Bool(bool),
+    Number(f64),
+    /// An ISO-8601 date, kept as its original `YYYY-MM-DD` string rather
+    /// than a calendar type since nothing else in this crate depends on
+    /// one yet.
+    Date(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuestionnaireError {
+    #[error("'{0}' is not a valid option; choose {1}")]
+    InvalidOption(String, String),
+    #[error("this question requires an answer")]
+    MissingRequired,
+    #[error("retry limit exceeded for question `{0}`")]
+    RetryLimitExceeded(String),
+    #[error("'{0}' is not yes/no")]
+    InvalidConfirm(String),
+    #[error("'{0}' is not a number")]
+    InvalidNumber(String),
+    #[error("{0} is below the minimum of {1}")]
+    NumberTooLow(f64, f64),
+    #[error("{0} is above the maximum of {1}")]
+    NumberTooHigh(f64, f64),
+    #[error("'{0}' is not a valid date (expected YYYY-MM-DD)")]
+    InvalidDate(String),
+}
+
+/// Parses and validates a raw answer string against `question`'s
+/// constraints. Returns the specific validation problem on failure so the
+/// caller can re-prompt with it instead of a generic "invalid answer".
+pub fn parse_questionnaire_response(
+    question: &Question,
+    raw: &str,
+) -> Result<AnswerValue, QuestionnaireError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        if question.required {
+            return Err(QuestionnaireError::MissingRequired);
+        }
+        return Ok(AnswerValue::Text(String::new()));
+    }
+
+    match &question.kind {
+        QuestionType::SingleSelect { options } => {
+            if options.iter().any(|o| o.eq_ignore_ascii_case(trimmed)) {
+                Ok(AnswerValue::Choice(trimmed.to_string()))
+            } else {
+                Err(QuestionnaireError::InvalidOption(trimmed.to_string(), options.join("/")))
+            }
+        }
+        QuestionType::MultiSelect { options } => {
+            let chosen: Vec<&str> = trimmed.split(',').map(|s| s.trim()).collect();
+            for c in &chosen {
+                if !options.iter().any(|o| o.eq_ignore_ascii_case(c)) {
+                    return Err(QuestionnaireError::InvalidOption(c.to_string(), options.join("/")));
+                }
+            }
+            Ok(AnswerValue::Choices(chosen.into_iter().map(String::from).collect()))
+        }
+        QuestionType::FreeText => Ok(AnswerValue::Text(trimmed.to_string())),
+        QuestionType::Confirm => match trimmed.to_ascii_lowercase().as_str() {
+            "y" | "yes" | "true" => Ok(AnswerValue::Bool(true)),
+            "n" | "no" | "false" => Ok(AnswerValue::Bool(false)),
+            _ => Err(QuestionnaireError::InvalidConfirm(trimmed.to_string())),
+        },
+        QuestionType::Number { min, max } => {
+            let value: f64 = trimmed
+                .parse()
+                .map_err(|_| QuestionnaireError::InvalidNumber(trimmed.to_string()))?;
+            if let Some(min) = min {
+                if value < *min {
+                    return Err(QuestionnaireError::NumberTooLow(value, *min));
+                }
+            }
+            if let Some(max) = max {
+                if value > *max {
+                    return Err(QuestionnaireError::NumberTooHigh(value, *max));
+                }
+            }
+            Ok(AnswerValue::Number(value))
+        }
+        QuestionType::Date => {
+            if parse_iso_date(trimmed).is_some() {
+                Ok(AnswerValue::Date(trimmed.to_string()))
+            } else {
+                Err(QuestionnaireError::InvalidDate(trimmed.to_string()))
+            }
+        }
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date, returning `(year, month, day)` if the
+/// format and field ranges are plausible. Doesn't account for per-month
+/// day counts (e.g. accepts 2024-02-30) — good enough to catch malformed
+/// input without pulling in a calendar library for this one check.
+fn parse_iso_date(s: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = s.split('-');
+    let year: u32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+/// Bounded re-prompt loop: calls `ask` (which presents `question` plus, on
+/// a retry, the specific validation problem from the previous attempt)
+/// once plus up to `max_retries` more times, returning the first valid
+/// answer or
+/// [`QuestionnaireError::RetryLimitExceeded`] once exhausted.
+pub async fn ask_until_valid<A, F>(
+    question: &Question,
+    max_retries: usize,
+    mut ask: A,
+) -> Result<AnswerValue, QuestionnaireError>
+where
+    A: FnMut(&Question, Option<&QuestionnaireError>) -> F,
+    F: std::future::Future<Output = String>,
+{
+    let mut last_error = None;
+    for _ in 0..=max_retries {
+        let raw = ask(question, last_error.as_ref()).await;
+        match parse_questionnaire_response(question, &raw) {
+            Ok(answer) => return Ok(answer),
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(QuestionnaireError::RetryLimitExceeded(question.id.clone()))
+}
@@ -0,0 +1,3 @@
+//! Credential security: encryption at rest and related CLI tooling.
+
+pub mod secure_store;
@@ -0,0 +1,219 @@
+//! Moving sessions between machines: [`export_sessions`] packages selected
+//! session files (optionally encrypted via [`SecureStore`]) into a single
+//! bundle file, and [`import_sessions`] restores them.
+//!
+//! There's no tar/zstd dependency in this crate, so the bundle is a simple
+//! JSON-lines format: a manifest line with a schema version (so a future
+//! format change can migrate older bundles on import) followed by one line
+//! per session. Session bytes are base64-encoded since an encrypted
+//! session's ciphertext isn't valid UTF-8.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::security::secure_store::SecureStore;
+
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionBundleError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed bundle: {0}")]
+    Malformed(String),
+    #[error("unsupported bundle schema version {0} (this build supports up to {CURRENT_SCHEMA_VERSION})")]
+    UnsupportedSchema(u32),
+    #[error("session `{0}` not found")]
+    NotFound(String),
+    #[error("decryption failed for session `{0}`: {1}")]
+    Decrypt(String, crate::security::secure_store::SecureStoreError),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportManifest {
+    schema_version: u32,
+    encrypted: bool,
+    sessions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedSession {
+    name: String,
+    content_b64: String,
+}
+
+/// Packages `names` (session file names, as returned by
+/// [`crate::session::list_sessions`]) from `sessions_dir` into a single
+/// bundle written to `out_path`. When `secure_store` is given, each
+/// session's bytes are encrypted before being embedded.
+pub fn export_sessions(
+    sessions_dir: &Path,
+    names: &[String],
+    out_path: &Path,
+    secure_store: Option<&SecureStore>,
+) -> Result<(), SessionBundleError> {
+    let mut lines = Vec::with_capacity(names.len() + 1);
+    let manifest = ExportManifest {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        encrypted: secure_store.is_some(),
+        sessions: names.to_vec(),
+    };
+    lines.push(serde_json::to_string(&manifest).expect("manifest serializes"));
+
+    for name in names {
+        let path = sessions_dir.join(name);
+        let contents = std::fs::read(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                SessionBundleError::NotFound(name.clone())
+            } else {
+                SessionBundleError::Io(e)
+            }
+        })?;
+        let bytes = match secure_store {
+            Some(store) => store.encrypt(&contents),
+            None => contents,
+        };
+        let exported = ExportedSession {
+            name: name.clone(),
+            content_b64: base64_encode(&bytes),
+        };
+        lines.push(serde_json::to_string(&exported).expect("exported session serializes"));
+    }
+
+    let tmp_path = out_path.with_extension("bundle.writing");
+    std::fs::write(&tmp_path, lines.join("\n") + "\n")?;
+    std::fs::rename(&tmp_path, out_path)?;
+    Ok(())
+}
+
+/// Restores every session in the bundle at `bundle_path` into
+/// `sessions_dir`, resolving name collisions by appending a numeric suffix.
+/// All sessions are staged first; if any one fails to decode or decrypt,
+/// nothing is written to `sessions_dir` at all.
+pub fn import_sessions(
+    bundle_path: &Path,
+    sessions_dir: &Path,
+    secure_store: Option<&SecureStore>,
+) -> Result<Vec<String>, SessionBundleError> {
+    let contents = std::fs::read_to_string(bundle_path)?;
+    let mut lines = contents.lines().filter(|l| !l.trim().is_empty());
+
+    let manifest_line = lines
+        .next()
+        .ok_or_else(|| SessionBundleError::Malformed("bundle is empty".to_string()))?;
+    let manifest: ExportManifest = serde_json::from_str(manifest_line)
+        .map_err(|e| SessionBundleError::Malformed(format!("bad manifest: {e}")))?;
+    if manifest.schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(SessionBundleError::UnsupportedSchema(manifest.schema_version));
+    }
+
+    std::fs::create_dir_all(sessions_dir)?;
+    let existing: std::collections::HashSet<String> = crate::session::list_sessions(sessions_dir)?
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+
+    // Stage every session to a `.importing` file first so a decode or
+    // decrypt failure partway through the bundle leaves `sessions_dir`
+    // untouched; only once all are staged do we rename them into place.
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut final_names = Vec::new();
+    let result = (|| {
+        for line in lines {
+            let exported: ExportedSession = serde_json::from_str(line)
+                .map_err(|e| SessionBundleError::Malformed(format!("bad session entry: {e}")))?;
+            let bytes = base64_decode(&exported.content_b64)
+                .ok_or_else(|| SessionBundleError::Malformed(format!("bad base64 for session `{}`", exported.name)))?;
+            let contents = match secure_store {
+                Some(store) => store
+                    .decrypt(&bytes)
+                    .map_err(|e| SessionBundleError::Decrypt(exported.name.clone(), e))?,
+                None => bytes,
+            };
+
+            let final_name = unique_name(&exported.name, &existing, &final_names);
+            let final_path = sessions_dir.join(&final_name);
+            let staging_path = sessions_dir.join(format!("{final_name}.importing"));
+            std::fs::write(&staging_path, &contents)?;
+            staged.push((staging_path, final_path));
+            final_names.push(final_name);
+        }
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        for (staging_path, _) in &staged {
+            let _ = std::fs::remove_file(staging_path);
+        }
+        return Err(err);
+    }
+
+    for (staging_path, final_path) in &staged {
+        std::fs::rename(staging_path, final_path)?;
+    }
+    Ok(final_names)
+}
+
+/// Picks a name that collides with neither the sessions already on disk
+/// nor another session already staged earlier in this same import, by
+/// appending an incrementing numeric suffix before the extension.
+fn unique_name(name: &str, existing: &std::collections::HashSet<String>, staged_so_far: &[String]) -> String {
+    if !existing.contains(name) && !staged_so_far.contains(&name.to_string()) {
+        return name.to_string();
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) => (stem.to_string(), format!(".{ext}")),
+        None => (name.to_string(), String::new()),
+    };
+    for suffix in 1.. {
+        let candidate = format!("{stem} ({suffix}){ext}");
+        if !existing.contains(&candidate) && !staged_so_far.contains(&candidate) {
+            return candidate;
+        }
+    }
+    unreachable!("suffix range is unbounded")
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => BASE64_ALPHABET[(b2 & 0x3f) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u8> {
+        BASE64_ALPHABET.iter().position(|&b| b == c).map(|p| p as u8)
+    }
+
+    let trimmed = encoded.trim_end_matches('=');
+    let mut out = Vec::with_capacity(trimmed.len() * 3 / 4 + 3);
+    let chars: Vec<u8> = trimmed.bytes().collect();
+    for chunk in chars.chunks(4) {
+        let v: Vec<u8> = chunk.iter().map(|&c| value(c)).collect::<Option<Vec<u8>>>()?;
+        out.push((v[0] << 2) | (v.get(1).copied().unwrap_or(0) >> 4));
+        if v.len() > 2 {
+            out.push((v[1] << 4) | (v[2] >> 2));
+        }
+        if v.len() > 3 {
+            out.push((v[2] << 6) | v[3]);
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,51 @@
+//! Conversation sessions: persistence, lookup, and the `--resume`
+//! resolution used by the chat CLI.
+
+pub mod export;
+pub mod storage;
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::cli::chat::ResumeTarget;
+
+#[derive(Debug, Clone)]
+pub struct SessionMeta {
+    pub name: String,
+    pub path: PathBuf,
+    pub modified: SystemTime,
+}
+
+/// Lists sessions stored under `sessions_dir` (one file per conversation),
+/// most recently modified first.
+pub fn list_sessions(sessions_dir: &Path) -> std::io::Result<Vec<SessionMeta>> {
+    let mut sessions = Vec::new();
+    if !sessions_dir.exists() {
+        return Ok(sessions);
+    }
+    for entry in std::fs::read_dir(sessions_dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        sessions.push(SessionMeta {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            path: entry.path(),
+            modified: metadata.modified()?,
+        });
+    }
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.modified));
+    Ok(sessions)
+}
+
+/// Resolve a `--resume`/`--resume-session` request against the sessions
+/// directory. Returns `None` when `target` is `ResumeTarget::None` or no
+/// matching session exists.
+pub fn resolve_resume_target(sessions_dir: &Path, target: &ResumeTarget) -> std::io::Result<Option<SessionMeta>> {
+    match target {
+        ResumeTarget::None => Ok(None),
+        ResumeTarget::MostRecent => Ok(list_sessions(sessions_dir)?.into_iter().next()),
+        ResumeTarget::Named(name) => Ok(list_sessions(sessions_dir)?.into_iter().find(|s| &s.name == name)),
+    }
+}
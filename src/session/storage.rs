@@ -0,0 +1,76 @@
+//! Persistent storage for artifacts that outlive a single turn — sessions
+//! live under `sessions/`, plans under `plans/`, both relative to the
+//! `.tark/` project directory.
+
+use std::path::{Path, PathBuf};
+
+use crate::agent::plan::Plan;
+
+pub struct TarkStorage {
+    root: PathBuf,
+}
+
+impl TarkStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn plans_dir(&self) -> PathBuf {
+        self.root.join("plans")
+    }
+
+    fn rules_dir(&self) -> PathBuf {
+        self.root.join("rules")
+    }
+
+    /// Loads a named rule's Markdown content for `tark chat --append-rule
+    /// <name>`, from `rules/<name>.md`. Returns `None` if no such rule is
+    /// stored.
+    pub fn load_rule(&self, name: &str) -> std::io::Result<Option<String>> {
+        let path = self.rules_dir().join(format!("{name}.md"));
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(path).map(Some)
+    }
+
+    /// Persists `plan` as Markdown, named after a slug of its goal so
+    /// repeated plans for the same goal are easy to find. Returns the path
+    /// written to.
+    pub fn save_plan(&self, plan: &Plan) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(self.plans_dir())?;
+        let path = self.plans_dir().join(format!("{}.md", slugify(&plan.goal)));
+        std::fs::write(&path, plan.to_markdown())?;
+        Ok(path)
+    }
+
+    pub fn load_plan_markdown(&self, path: &Path) -> std::io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    /// Loads the plan saved under `name` (the slug printed when it was
+    /// created), for `tark plan run <name>`. Returns `None` if the file
+    /// exists but can't be parsed as a plan, or doesn't exist.
+    pub fn load_plan(&self, name: &str) -> std::io::Result<Option<Plan>> {
+        let path = self.plans_dir().join(format!("{name}.md"));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let markdown = std::fs::read_to_string(path)?;
+        Ok(Plan::from_markdown(&markdown))
+    }
+}
+
+fn slugify(text: &str) -> String {
+    let slug: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "plan".to_string()
+    } else {
+        slug.chars().take(60).collect()
+    }
+}
@@ -0,0 +1,243 @@
+//! `read_file`/`write_file`/`edit_file` tools: the file-touching tools
+//! confined to a [`SandboxRoot`] so an agent-supplied path can't read or
+//! write outside the workspace, even via `..` traversal or a symlink.
+//! `apply_unified_diff` applies a unified diff to a file atomically —
+//! either every hunk applies cleanly or the file is left untouched.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::tools::registry::{RiskLevel, ToolDefinition};
+use crate::tools::sandbox::SandboxRoot;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EditError {
+    #[error("hunk at line {0} does not match file contents")]
+    HunkMismatch(usize),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+struct Hunk {
+    /// 1-based starting line in the original file.
+    start: usize,
+    /// Lines to remove, in order, as they must appear starting at `start`.
+    remove: Vec<String>,
+    /// Lines to insert in their place.
+    insert: Vec<String>,
+}
+
+/// Apply a minimal unified-diff-like patch (`@@ -start,count +start,count @@`
+/// headers followed by ` `/`-`/`+` prefixed lines) to `path`. The file is
+/// only written if every hunk matches; a mismatch leaves it untouched.
+pub fn apply_unified_diff(path: &Path, diff: &str) -> Result<(), EditError> {
+    let original = fs::read_to_string(path)?;
+    let original_lines: Vec<&str> = original.lines().collect();
+    let hunks = parse_hunks(diff);
+
+    let mut result: Vec<String> = Vec::new();
+    let mut cursor = 0usize; // 0-based index into original_lines already copied
+
+    for hunk in &hunks {
+        let hunk_start = hunk.start.saturating_sub(1);
+        if hunk_start < cursor || hunk_start + hunk.remove.len() > original_lines.len() {
+            return Err(EditError::HunkMismatch(hunk.start));
+        }
+        result.extend(original_lines[cursor..hunk_start].iter().map(|s| s.to_string()));
+
+        for (offset, expected) in hunk.remove.iter().enumerate() {
+            if original_lines[hunk_start + offset] != expected {
+                return Err(EditError::HunkMismatch(hunk.start));
+            }
+        }
+
+        result.extend(hunk.insert.iter().cloned());
+        cursor = hunk_start + hunk.remove.len();
+    }
+    result.extend(original_lines[cursor..].iter().map(|s| s.to_string()));
+
+    let new_contents = result.join("\n") + "\n";
+    let tmp_path = path.with_extension("tark-edit-tmp");
+    fs::write(&tmp_path, new_contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn parse_hunks(diff: &str) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("@@ -") {
+            if let Some(existing) = current.take() {
+                hunks.push(existing);
+            }
+            let start = rest
+                .split([',', ' '])
+                .next()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1);
+            current = Some(Hunk {
+                start,
+                remove: Vec::new(),
+                insert: Vec::new(),
+            });
+        } else if let Some(hunk) = current.as_mut() {
+            if let Some(rest) = line.strip_prefix('-') {
+                hunk.remove.push(rest.to_string());
+            } else if let Some(rest) = line.strip_prefix('+') {
+                hunk.insert.push(rest.to_string());
+            } else if let Some(rest) = line.strip_prefix(' ') {
+                hunk.remove.push(rest.to_string());
+                hunk.insert.push(rest.to_string());
+            }
+        }
+    }
+    if let Some(hunk) = current {
+        hunks.push(hunk);
+    }
+    hunks
+}
+
+/// Builds the `read_file` tool, confined to `sandbox`.
+pub fn read_file_tool(sandbox: SandboxRoot) -> ToolDefinition {
+    let sandbox = Arc::new(sandbox);
+    ToolDefinition {
+        name: "read_file".to_string(),
+        risk: crate::tools::tools_db::classify("read_file")
+            .map(|c| c.risk)
+            .unwrap_or(RiskLevel::ReadOnly),
+        timeout: Duration::from_secs(10),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": { "path": {"type": "string"} },
+            "required": ["path"],
+        })),
+        handler: Box::new(move |args, _cancel| {
+            let sandbox = sandbox.clone();
+            Box::pin(async move {
+                let path = args
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("read_file tool requires a `path` argument"))?;
+                let resolved = sandbox.resolve(Path::new(path))?;
+                let contents = fs::read_to_string(resolved)?;
+                Ok(serde_json::json!({ "contents": contents }))
+            })
+        }),
+    }
+}
+
+/// Builds the `write_file` tool, confined to `sandbox`.
+pub fn write_file_tool(sandbox: SandboxRoot) -> ToolDefinition {
+    let sandbox = Arc::new(sandbox);
+    ToolDefinition {
+        name: "write_file".to_string(),
+        risk: crate::tools::tools_db::classify("write_file")
+            .map(|c| c.risk)
+            .unwrap_or(RiskLevel::Mutating),
+        timeout: Duration::from_secs(10),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {"type": "string"},
+                "contents": {"type": "string"},
+            },
+            "required": ["path", "contents"],
+        })),
+        handler: Box::new(move |args, _cancel| {
+            let sandbox = sandbox.clone();
+            Box::pin(async move {
+                let path = args
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("write_file tool requires a `path` argument"))?;
+                let contents = args
+                    .get("contents")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("write_file tool requires a `contents` argument"))?;
+                let resolved = sandbox.resolve(Path::new(path))?;
+                fs::write(resolved, contents)?;
+                Ok(serde_json::json!({ "ok": true }))
+            })
+        }),
+    }
+}
+
+/// Builds the `edit_file` tool, confined to `sandbox`.
+pub fn edit_file_tool(sandbox: SandboxRoot) -> ToolDefinition {
+    let sandbox = Arc::new(sandbox);
+    ToolDefinition {
+        name: "edit_file".to_string(),
+        risk: crate::tools::tools_db::classify("edit_file")
+            .map(|c| c.risk)
+            .unwrap_or(RiskLevel::Mutating),
+        timeout: Duration::from_secs(10),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {"type": "string"},
+                "diff": {"type": "string"},
+            },
+            "required": ["path", "diff"],
+        })),
+        handler: Box::new(move |args, _cancel| {
+            let sandbox = sandbox.clone();
+            Box::pin(async move {
+                let path = args
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("edit_file tool requires a `path` argument"))?;
+                let diff = args
+                    .get("diff")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("edit_file tool requires a `diff` argument"))?;
+                let resolved = sandbox.resolve(Path::new(path))?;
+                apply_unified_diff(&resolved, diff)?;
+                Ok(serde_json::json!({ "ok": true }))
+            })
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox_in(dir: &Path) -> SandboxRoot {
+        SandboxRoot::new(dir).expect("sandbox root should canonicalize")
+    }
+
+    #[test]
+    fn apply_unified_diff_rejects_mismatched_hunk() {
+        let dir = std::env::temp_dir().join(format!("tark-edit-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.txt");
+        fs::write(&file, "one\ntwo\nthree\n").unwrap();
+
+        let diff = "@@ -2,1 +2,1 @@\n-nope\n+TWO\n";
+        let err = apply_unified_diff(&file, diff).unwrap_err();
+        assert!(matches!(err, EditError::HunkMismatch(2)));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "one\ntwo\nthree\n");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn read_write_tools_confined_to_sandbox() {
+        let dir = std::env::temp_dir().join(format!("tark-file-tools-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let sandbox = sandbox_in(&dir);
+
+        let inside = sandbox.resolve(Path::new("note.txt")).unwrap();
+        fs::write(&inside, "hello").unwrap();
+        assert_eq!(fs::read_to_string(&inside).unwrap(), "hello");
+
+        let escape = sandbox.resolve(Path::new("../outside.txt"));
+        assert!(escape.is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,47 @@
+//! `git_context` tool: lets the agent inspect diffs and blame without
+//! shelling out through the generic shell tool, so these read-only
+//! operations don't need shell approval.
+
+use std::process::Command;
+
+#[derive(Debug, Clone)]
+pub struct GitContextRequest {
+    pub path: String,
+    pub kind: GitContextKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum GitContextKind {
+    /// `git diff` against the working tree, or `git diff <rev>` if given.
+    Diff { rev: Option<String> },
+    /// `git blame` for the given path.
+    Blame,
+    /// `git log` for the given path, most recent first.
+    Log { max_entries: usize },
+}
+
+pub fn run(request: &GitContextRequest) -> anyhow::Result<String> {
+    let args: Vec<String> = match &request.kind {
+        GitContextKind::Diff { rev: Some(rev) } => {
+            vec!["diff".into(), rev.clone(), "--".into(), request.path.clone()]
+        }
+        GitContextKind::Diff { rev: None } => vec!["diff".into(), "--".into(), request.path.clone()],
+        GitContextKind::Blame => vec!["blame".into(), "--".into(), request.path.clone()],
+        GitContextKind::Log { max_entries } => vec![
+            "log".into(),
+            format!("-n{max_entries}"),
+            "--".into(),
+            request.path.clone(),
+        ],
+    };
+
+    let output = Command::new("git").args(&args).output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,44 @@
+//! `.tarkignore` (gitignore syntax, including negation and directory
+//! patterns) merged with `config.workspace.ignore_patterns`, giving the
+//! search tool, `tark explain`, and anything else that walks the repo one
+//! consistent notion of what to skip instead of each inventing its own
+//! glob rules.
+
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::config::WorkspaceConfig;
+
+pub struct IgnoreRules {
+    matcher: Gitignore,
+}
+
+impl IgnoreRules {
+    /// Builds the matcher for `root`: a `.tarkignore` file at the
+    /// workspace root (if present) plus `config.ignore_patterns`, added in
+    /// that order — a later pattern can un-ignore something an earlier one
+    /// excluded via a `!negation`, matching gitignore's own precedence.
+    pub fn load(root: &Path, config: &WorkspaceConfig) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+        let tarkignore = root.join(".tarkignore");
+        if tarkignore.exists() {
+            // A malformed individual line is skipped rather than failing
+            // the whole build; we already know the file itself exists.
+            let _ = builder.add(&tarkignore);
+        }
+        for pattern in &config.ignore_patterns {
+            let _ = builder.add_line(None, pattern);
+        }
+        let matcher = builder
+            .build()
+            .unwrap_or_else(|_| GitignoreBuilder::new(root).build().expect("a builder with no patterns always builds"));
+        Self { matcher }
+    }
+
+    /// `true` if `path` should be skipped. `path` may be absolute or
+    /// relative to the root this was built with.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.matcher.matched(path, is_dir).is_ignore()
+    }
+}
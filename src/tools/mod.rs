@@ -0,0 +1,14 @@
+//! Agent tools: the registry, execution sandboxing, and individual tool
+//! implementations (see `docs/TOOL_CALL_ARCHITECTURE.md`).
+
+pub mod edit;
+pub mod git_context;
+pub mod ignore_rules;
+pub mod output_store;
+pub mod registry;
+pub mod sandbox;
+pub mod schema;
+pub mod search;
+pub mod shell;
+pub mod tools_db;
+pub mod web_fetch;
@@ -0,0 +1,148 @@
+//! Full tool output retained on disk when a result is too large to put
+//! back in context directly. The model gets a truncated preview plus an
+//! id it can pass to the `fetch_tool_output` tool to read more.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::truncate_at_char_boundary;
+
+/// Results larger than this are truncated, with the full text spilled to
+/// `.tark/tool_outputs/<id>`.
+pub const TRUNCATE_THRESHOLD_BYTES: usize = 8_000;
+
+/// How much of the head/tail to keep around the truncation marker.
+const KEEP_EDGE_BYTES: usize = 2_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutputStoreError {
+    #[error("tool output `{0}` not found")]
+    NotFound(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// A half-open `[start, end)` byte range into a stored output, used by
+/// `fetch_tool_output` to page through content too large to return in one
+/// go.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ByteRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+pub struct ToolOutputStore {
+    dir: PathBuf,
+}
+
+/// Default location, relative to the project root, that `ChatAgent` uses
+/// unless overridden.
+pub const DEFAULT_OUTPUT_DIR: &str = ".tark/tool_outputs";
+
+impl Default for ToolOutputStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_OUTPUT_DIR)
+    }
+}
+
+impl ToolOutputStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// If `output` exceeds [`TRUNCATE_THRESHOLD_BYTES`], writes the full
+    /// text to disk under a fresh id and returns `(preview, Some(id))`.
+    /// Otherwise returns `(output, None)` unchanged.
+    pub fn store_if_large(&self, output: &str) -> std::io::Result<(String, Option<String>)> {
+        if output.len() <= TRUNCATE_THRESHOLD_BYTES {
+            return Ok((output.to_string(), None));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path_for(&id), output)?;
+
+        let head = truncate_at_char_boundary(output, KEEP_EDGE_BYTES);
+        let tail_start = output.len() - KEEP_EDGE_BYTES.min(output.len());
+        let mut tail_start = tail_start;
+        while tail_start < output.len() && !output.is_char_boundary(tail_start) {
+            tail_start += 1;
+        }
+        let tail = &output[tail_start..];
+
+        let preview = format!(
+            "{head}\n… [truncated {} bytes; full output available via fetch_tool_output(id = \"{id}\")] …\n{tail}",
+            output.len() - head.len() - tail.len()
+        );
+        Ok((preview, Some(id)))
+    }
+
+    /// Reads back `range` of the stored output for `id`, or the whole
+    /// thing if `range` is `None`.
+    pub fn fetch(&self, id: &str, range: Option<ByteRange>) -> Result<String, OutputStoreError> {
+        let path = self.path_for(id);
+        if !path.exists() {
+            return Err(OutputStoreError::NotFound(id.to_string()));
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(match range {
+            Some(r) => {
+                let mut end = r.end.min(contents.len());
+                while end > 0 && !contents.is_char_boundary(end) {
+                    end -= 1;
+                }
+                let mut start = r.start.min(end);
+                while start < end && !contents.is_char_boundary(start) {
+                    start += 1;
+                }
+                contents[start..end].to_string()
+            }
+            None => contents,
+        })
+    }
+
+    fn path_for(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+}
+
+/// Builds the `fetch_tool_output` tool definition for registration with a
+/// [`crate::tools::registry::ToolRegistry`]: takes `{"id": ..., "range":
+/// {"start": ..., "end": ...} | null}` and returns the stored text.
+pub fn fetch_tool_output_tool(store: std::sync::Arc<ToolOutputStore>) -> crate::tools::registry::ToolDefinition {
+    use std::time::Duration;
+
+    crate::tools::registry::ToolDefinition {
+        name: "fetch_tool_output".to_string(),
+        risk: crate::tools::tools_db::classify("fetch_tool_output")
+            .map(|c| c.risk)
+            .unwrap_or(crate::tools::registry::RiskLevel::ReadOnly),
+        timeout: Duration::from_secs(5),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                "range": {"type": "object"},
+            },
+            "required": ["id"],
+        })),
+        handler: Box::new(move |args, _cancel| {
+            let store = store.clone();
+            Box::pin(async move {
+                let id = args
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("fetch_tool_output requires an `id` argument"))?;
+                let range = args
+                    .get("range")
+                    .filter(|v| !v.is_null())
+                    .map(|v| serde_json::from_value::<ByteRange>(v.clone()))
+                    .transpose()?;
+                let text = store.fetch(id, range)?;
+                Ok(serde_json::json!({ "text": text }))
+            })
+        }),
+    }
+}
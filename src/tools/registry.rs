@@ -0,0 +1,117 @@
+//! Tool registry: registration, risk categorization, and execution with
+//! per-tool timeouts and cancellation propagation.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::config::AgentToolsConfig;
+use crate::tools::schema::SchemaError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskLevel {
+    ReadOnly,
+    Mutating,
+    Destructive,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ToolError {
+    #[error("tool `{0}` timed out after {1:?}")]
+    Timeout(String, Duration),
+    #[error("tool `{0}` was cancelled")]
+    Cancelled(String),
+    #[error("tool `{0}` is not registered")]
+    NotFound(String),
+    #[error("tool `{0}` is not permitted by the configured allowlist/denylist")]
+    NotPermitted(String),
+    #[error("tool `{0}` arguments are invalid: {1}")]
+    InvalidArgs(String, SchemaError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+type ToolFuture = Pin<Box<dyn Future<Output = anyhow::Result<serde_json::Value>> + Send>>;
+
+pub struct ToolDefinition {
+    pub name: String,
+    pub risk: RiskLevel,
+    /// Maximum time this tool is allowed to run before its execution is
+    /// cancelled and a timeout error is returned.
+    pub timeout: Duration,
+    /// JSON Schema describing this tool's arguments, sent to the LLM
+    /// alongside its name/description. `None` means the tool accepts
+    /// whatever its handler is willing to parse, with no validation ahead
+    /// of the call. See [`crate::tools::schema::validate`].
+    pub input_schema: Option<serde_json::Value>,
+    pub handler: Box<dyn Fn(serde_json::Value, CancellationToken) -> ToolFuture + Send + Sync>,
+}
+
+#[derive(Default)]
+pub struct ToolRegistry {
+    tools: HashMap<String, ToolDefinition>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, definition: ToolDefinition) {
+        self.tools.insert(definition.name.clone(), definition);
+    }
+
+    /// The configured risk level of `name`, or `None` if it isn't
+    /// registered.
+    pub fn risk(&self, name: &str) -> Option<RiskLevel> {
+        self.tools.get(name).map(|tool| tool.risk)
+    }
+
+    /// The configured input schema of `name`, or `None` if it isn't
+    /// registered or was registered without one.
+    pub fn input_schema(&self, name: &str) -> Option<&serde_json::Value> {
+        self.tools.get(name).and_then(|tool| tool.input_schema.as_ref())
+    }
+
+    /// Run `name` with `args`, enforcing its configured timeout and
+    /// honoring `parent_cancellation` so cancelling the surrounding agent
+    /// turn cancels every in-flight tool call too. Arguments are validated
+    /// against the tool's `input_schema` (if it has one) before the
+    /// handler ever runs; `ChatAgent::run` already validates and retries
+    /// with feedback ahead of this call, so this check is a backstop for
+    /// any caller that invokes a tool directly without going through that
+    /// loop — mirroring the `shell_blocked` upstream-check-plus-backstop
+    /// pattern in `crate::tools::shell`.
+    pub async fn call(
+        &self,
+        name: &str,
+        args: serde_json::Value,
+        parent_cancellation: CancellationToken,
+        tools_config: &AgentToolsConfig,
+    ) -> Result<serde_json::Value, ToolError> {
+        if !tools_config.is_allowed(name) {
+            return Err(ToolError::NotPermitted(name.to_string()));
+        }
+        let tool = self
+            .tools
+            .get(name)
+            .ok_or_else(|| ToolError::NotFound(name.to_string()))?;
+        if let Some(schema) = &tool.input_schema {
+            crate::tools::schema::validate(&args, schema).map_err(|err| ToolError::InvalidArgs(name.to_string(), err))?;
+        }
+        let child = parent_cancellation.child_token();
+        let run = (tool.handler)(args, child.clone());
+
+        tokio::select! {
+            result = run => result.map_err(ToolError::Other),
+            _ = child.cancelled() => Err(ToolError::Cancelled(name.to_string())),
+            _ = tokio::time::sleep(tool.timeout) => {
+                child.cancel();
+                Err(ToolError::Timeout(name.to_string(), tool.timeout))
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Confines file tools to a workspace root so they can't read or write
+//! outside it, even via `..` traversal or symlinks.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SandboxError {
+    #[error("path `{0}` escapes the sandbox root")]
+    Escapes(PathBuf),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A workspace root that file tools must resolve every path against.
+pub struct SandboxRoot {
+    root: PathBuf,
+}
+
+impl SandboxRoot {
+    pub fn new(root: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let root = fs::canonicalize(root.into())?;
+        Ok(Self { root })
+    }
+
+    /// Resolve `requested` (which may be relative or absolute) against the
+    /// sandbox root and verify the canonicalized result is still inside
+    /// it, following symlinks to catch escapes they introduce.
+    pub fn resolve(&self, requested: &Path) -> Result<PathBuf, SandboxError> {
+        self.resolve_from(&self.root.clone(), requested)
+    }
+
+    /// Like [`resolve`](Self::resolve), but relative paths are joined
+    /// against `base` instead of the sandbox root. `base` itself must
+    /// already be inside the sandbox — callers resolve it (e.g. via
+    /// `resolve`) before using it here. Lets tools confine a per-call
+    /// working directory (like the shell tool's `cwd` argument) while
+    /// still checking the final path never escapes the root.
+    pub fn resolve_from(&self, base: &Path, requested: &Path) -> Result<PathBuf, SandboxError> {
+        let joined = if requested.is_absolute() {
+            requested.to_path_buf()
+        } else {
+            base.join(requested)
+        };
+
+        let canonical = if joined.exists() {
+            fs::canonicalize(&joined)?
+        } else {
+            // For paths that don't exist yet (e.g. a file about to be
+            // created), resolve `..`/`.` lexically first so a multi-level
+            // escape is caught even when the intermediate directories
+            // (like `sub/` in `sub/../../escape.txt`) don't exist on disk,
+            // then canonicalize the nearest ancestor that *does* exist and
+            // re-attach the rest.
+            let normalized = lexically_normalize(&joined);
+            let mut existing_ancestor = normalized.as_path();
+            let mut missing_suffix = Vec::new();
+            while !existing_ancestor.exists() {
+                missing_suffix.push(existing_ancestor.file_name().unwrap_or_default().to_os_string());
+                existing_ancestor = existing_ancestor.parent().unwrap_or(&self.root);
+            }
+            let mut canonical = fs::canonicalize(existing_ancestor)?;
+            for part in missing_suffix.into_iter().rev() {
+                canonical.push(part);
+            }
+            canonical
+        };
+
+        if canonical.starts_with(&self.root) {
+            Ok(canonical)
+        } else {
+            Err(SandboxError::Escapes(requested.to_path_buf()))
+        }
+    }
+}
+
+/// Resolves `.`/`..` components of `path` purely lexically (no filesystem
+/// access), so a `..` escape is detected even when the path it traverses
+/// doesn't exist yet. `path` is expected to be absolute (callers always
+/// join onto an already-canonical base), so a leading `..` climbs past the
+/// root component rather than being dropped.
+fn lexically_normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !result.pop() {
+                    result.push("..");
+                }
+            }
+            other => result.push(other.as_os_str()),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tark-sandbox-test-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_allows_paths_inside_root() {
+        let dir = temp_dir("inside");
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+
+        let resolved = sandbox.resolve(Path::new("a.txt")).unwrap();
+        assert!(resolved.starts_with(fs::canonicalize(&dir).unwrap()));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_rejects_dot_dot_traversal() {
+        let dir = temp_dir("dotdot");
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+
+        let err = sandbox.resolve(Path::new("../escape.txt")).unwrap_err();
+        assert!(matches!(err, SandboxError::Escapes(_)));
+
+        let err = sandbox.resolve(Path::new("sub/../../escape.txt")).unwrap_err();
+        assert!(matches!(err, SandboxError::Escapes(_)));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn resolve_rejects_symlink_escape() {
+        let outside = temp_dir("outside");
+        let dir = temp_dir("symlink-root");
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+
+        let link = dir.join("escape");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+        #[cfg(unix)]
+        {
+            let err = sandbox.resolve(Path::new("escape")).unwrap_err();
+            assert!(matches!(err, SandboxError::Escapes(_)));
+
+            // A path reached *through* the symlink must also be rejected,
+            // not just the symlink itself.
+            let err = sandbox.resolve(Path::new("escape/secret.txt"));
+            assert!(err.is_err());
+        }
+
+        fs::remove_dir_all(&dir).ok();
+        fs::remove_dir_all(&outside).ok();
+    }
+
+    #[test]
+    fn resolve_from_confines_relative_paths_to_base() {
+        let dir = temp_dir("resolve-from");
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+        let base = sandbox.resolve(Path::new(".")).unwrap();
+
+        let err = sandbox.resolve_from(&base, Path::new("../../etc/passwd")).unwrap_err();
+        assert!(matches!(err, SandboxError::Escapes(_)));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,77 @@
+//! Minimal JSON-Schema validator for tool call arguments. Only the subset
+//! of the spec a `ToolDefinition.input_schema` actually needs —
+//! `type`/`required`/`properties` — is supported; there's no use for a
+//! general-purpose schema validator here, and no such crate is already a
+//! dependency of this tree.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SchemaError {
+    #[error("missing required field `{0}`")]
+    MissingField(String),
+    #[error("field `{0}` has type `{1}`, expected `{2}`")]
+    WrongType(String, String, String),
+    #[error("tool arguments must be a JSON object")]
+    NotAnObject,
+}
+
+/// Validates `args` against `schema`'s `required` and `properties.*.type`
+/// keywords. A schema with no `properties` key is treated as accepting
+/// anything, so a tool can opt out of validation by omitting its schema
+/// rather than writing a permissive one.
+pub fn validate(args: &Value, schema: &Value) -> Result<(), SchemaError> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Ok(());
+    };
+    let args_obj = args.as_object().ok_or(SchemaError::NotAnObject)?;
+
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !args_obj.contains_key(name) {
+                return Err(SchemaError::MissingField(name.to_string()));
+            }
+        }
+    }
+
+    for (name, value) in args_obj {
+        let Some(expected_type) = properties.get(name).and_then(|p| p.get("type")).and_then(Value::as_str) else {
+            continue;
+        };
+        if !matches_type(value, expected_type) {
+            return Err(SchemaError::WrongType(
+                name.clone(),
+                json_type_name(value).to_string(),
+                expected_type.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        // An unrecognized `type` keyword isn't this validator's problem to
+        // enforce; let it through rather than rejecting every call.
+        _ => true,
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,475 @@
+//! Filesystem search tool with two modes: `files` (glob file-name
+//! matching) and `content` (regex search over file contents, with
+//! line/column and surrounding context). Both honor
+//! [`IgnoreRules`] (`.tarkignore` plus `WorkspaceConfig.ignore_patterns`)
+//! and run off the main async runtime via `spawn_blocking`, since walking
+//! a large tree or regex-scanning many files is blocking work that would
+//! otherwise stall the executor thread it runs on.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::tools::ignore_rules::IgnoreRules;
+use crate::tools::registry::{RiskLevel, ToolDefinition};
+use crate::tools::sandbox::SandboxRoot;
+
+/// Default/hard-cap result count for a `search` call when the caller
+/// doesn't set (or oversets) `max_results` — keeps one call from returning
+/// an unbounded flood of matches.
+const DEFAULT_MAX_RESULTS: usize = 200;
+const HARD_MAX_RESULTS: usize = 1000;
+
+/// Default/hard-cap context lines around a content match.
+const DEFAULT_CONTEXT_LINES: usize = 2;
+const HARD_MAX_CONTEXT_LINES: usize = 20;
+
+/// Total bytes a single `content` search will read across all files before
+/// stopping early (with `truncated: true`), so a search over a huge tree
+/// has a predictable cost instead of scanning every byte of every file.
+const MAX_BYTES_SCANNED: usize = 5_000_000;
+
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    pub path: String,
+    pub line: u32,
+    pub column: u32,
+    pub text: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+/// The result of [`search_content`]: the matches found (capped at
+/// `max_results`) and whether the search stopped early because it hit
+/// `max_results` or [`MAX_BYTES_SCANNED`] before finishing the tree.
+#[derive(Debug, Default)]
+pub struct ContentSearchResult {
+    pub matches: Vec<SearchMatch>,
+    pub truncated: bool,
+}
+
+/// Glob-match a pattern against a name. Supports `*` (any run of
+/// characters, including zero) and `?` (exactly one character), anchored
+/// to the full string — `*.test.*` must match the whole name, not just a
+/// substring of it. Any number of wildcards is supported; the previous
+/// implementation only anchored the first and last `*`-separated segment,
+/// so a pattern like `*.test.*` matched any name merely starting and
+/// ending with `.` regardless of what came between. Case-sensitive; see
+/// [`glob_match_case`] for callers (host/domain allowlists) that want
+/// case-insensitive matching instead.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    glob_match_case(pattern, name, true)
+}
+
+/// Like [`glob_match`], but `case_sensitive` controls whether letter case
+/// must match exactly. `shell_blocked`/`shell_always_ask` patterns stay
+/// case-sensitive (program names are), while a host allowlist is a
+/// natural case-insensitive match, since hostnames aren't
+/// case-significant.
+pub fn glob_match_case(pattern: &str, name: &str, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        matches(&pattern, &name)
+    } else {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        let name: Vec<char> = name.to_lowercase().chars().collect();
+        matches(&pattern, &name)
+    }
+}
+
+fn matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => matches(&pattern[1..], name) || (!name.is_empty() && matches(pattern, &name[1..])),
+        (Some('?'), Some(_)) => matches(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => matches(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+/// Finds file names under `root` matching `pattern` (see [`glob_match`]),
+/// honoring `ignore`. Stops early once `max_results` names are found;
+/// `truncated` is `true` when that happened, so a caller knows the tree
+/// wasn't fully walked.
+pub fn find_files(root: &Path, pattern: &str, ignore: &IgnoreRules, max_results: usize) -> anyhow::Result<(Vec<String>, bool)> {
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    for entry in walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path(), e.file_type().is_dir()))
+        .filter_map(Result::ok)
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if glob_match(pattern, name) {
+                if matches.len() >= max_results {
+                    truncated = true;
+                    break;
+                }
+                matches.push(entry.path().display().to_string());
+            }
+        }
+    }
+    Ok((matches, truncated))
+}
+
+/// Regex-searches file contents under `root`, honoring `ignore`. Returns
+/// up to `max_results` matches, each with `context_lines` lines of
+/// surrounding context on either side, and stops scanning once either cap
+/// is hit (see [`MAX_BYTES_SCANNED`]) rather than reading the whole tree.
+/// Runs entirely in-process (no `rg` dependency) so it behaves the same on
+/// every platform this crate supports; binary (non-UTF-8) files are
+/// skipped rather than erroring the whole search.
+pub fn search_content(
+    root: &Path,
+    query: &str,
+    ignore: &IgnoreRules,
+    max_results: usize,
+    context_lines: usize,
+) -> anyhow::Result<ContentSearchResult> {
+    let regex = regex::Regex::new(query)?;
+    let mut result = ContentSearchResult::default();
+    let mut bytes_scanned = 0usize;
+
+    'walk: for entry in walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !ignore.is_ignored(e.path(), e.file_type().is_dir()))
+        .filter_map(Result::ok)
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if bytes_scanned >= MAX_BYTES_SCANNED {
+            result.truncated = true;
+            break;
+        }
+
+        let Ok(contents) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        bytes_scanned += contents.len();
+        let Ok(text) = String::from_utf8(contents) else {
+            continue;
+        };
+
+        let lines: Vec<&str> = text.lines().collect();
+        for (idx, line) in lines.iter().enumerate() {
+            let Some(found) = regex.find(line) else {
+                continue;
+            };
+            if result.matches.len() >= max_results {
+                result.truncated = true;
+                break 'walk;
+            }
+            let before_start = idx.saturating_sub(context_lines);
+            let after_end = (idx + 1 + context_lines).min(lines.len());
+            result.matches.push(SearchMatch {
+                path: entry.path().display().to_string(),
+                line: (idx + 1) as u32,
+                column: (found.start() + 1) as u32,
+                text: (*line).to_string(),
+                context_before: lines[before_start..idx].iter().map(|s| s.to_string()).collect(),
+                context_after: lines[idx + 1..after_end].iter().map(|s| s.to_string()).collect(),
+            });
+        }
+    }
+    Ok(result)
+}
+
+fn clamp_max_results(args: &serde_json::Value) -> usize {
+    args.get("max_results")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_MAX_RESULTS)
+        .clamp(1, HARD_MAX_RESULTS)
+}
+
+fn clamp_context_lines(args: &serde_json::Value) -> usize {
+    args.get("context_lines")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_CONTEXT_LINES)
+        .min(HARD_MAX_CONTEXT_LINES)
+}
+
+fn render_match(m: &SearchMatch) -> serde_json::Value {
+    serde_json::json!({
+        "path": m.path,
+        "line": m.line,
+        "column": m.column,
+        "text": m.text,
+        "context_before": m.context_before,
+        "context_after": m.context_after,
+    })
+}
+
+/// Builds the `search` tool: `mode: "files"` globs file names (the
+/// original behavior), `mode: "content"` regex-searches file contents.
+/// `dir` (resolved against `sandbox` the same way `read_file`/`write_file`
+/// are, so a `dir` of `../..` can't walk outside the workspace) scopes
+/// either mode to a subtree, defaulting to the sandbox root. Both modes
+/// run the actual tree walk in [`tokio::task::spawn_blocking`] so a large
+/// tree doesn't stall the async runtime this handler runs on.
+pub fn search_tool(sandbox: SandboxRoot, ignore: IgnoreRules) -> ToolDefinition {
+    let sandbox = Arc::new(sandbox);
+    let ignore = Arc::new(ignore);
+    ToolDefinition {
+        name: "search".to_string(),
+        risk: crate::tools::tools_db::classify("search")
+            .map(|c| c.risk)
+            .unwrap_or(RiskLevel::ReadOnly),
+        timeout: Duration::from_secs(30),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "mode": {"type": "string", "enum": ["files", "content"]},
+                "pattern": {"type": "string", "description": "glob pattern for file names, required when mode is \"files\""},
+                "query": {"type": "string", "description": "regex to search file contents, required when mode is \"content\""},
+                "dir": {"type": "string"},
+                "max_results": {"type": "integer"},
+                "context_lines": {"type": "integer", "description": "lines of context around each match, content mode only"},
+            },
+            "required": ["mode"],
+        })),
+        handler: Box::new(move |args, _cancel| {
+            let sandbox = sandbox.clone();
+            let ignore = ignore.clone();
+            Box::pin(async move {
+                let mode = args.get("mode").and_then(|v| v.as_str()).unwrap_or("files").to_string();
+                let root = match args.get("dir").and_then(|v| v.as_str()) {
+                    Some(dir) => sandbox.resolve(Path::new(dir))?,
+                    None => sandbox.resolve(Path::new("."))?,
+                };
+                let max_results = clamp_max_results(&args);
+
+                match mode.as_str() {
+                    "files" => {
+                        let pattern = args
+                            .get("pattern")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| anyhow::anyhow!("search tool in `files` mode requires a `pattern` argument"))?
+                            .to_string();
+                        let (matches, truncated) =
+                            tokio::task::spawn_blocking(move || find_files(&root, &pattern, &ignore, max_results)).await??;
+                        Ok(serde_json::json!({ "matches": matches, "truncated": truncated }))
+                    }
+                    "content" => {
+                        let query = args
+                            .get("query")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| anyhow::anyhow!("search tool in `content` mode requires a `query` argument"))?
+                            .to_string();
+                        let context_lines = clamp_context_lines(&args);
+                        let result = tokio::task::spawn_blocking(move || {
+                            search_content(&root, &query, &ignore, max_results, context_lines)
+                        })
+                        .await??;
+                        Ok(serde_json::json!({
+                            "matches": result.matches.iter().map(render_match).collect::<Vec<_>>(),
+                            "truncated": result.truncated,
+                        }))
+                    }
+                    other => Err(anyhow::anyhow!("search tool `mode` must be \"files\" or \"content\", got `{other}`")),
+                }
+            })
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Table of (pattern, name, expected) cases, including the ones a
+    /// naive "anchor only the first/last `*`-separated segment"
+    /// implementation used to get wrong (see `glob_match`'s doc comment).
+    /// This matcher feeds `shell_blocked`/`shell_always_ask`, so it's a
+    /// security control, not just a display nicety — worth locking in.
+    const CASES: &[(&str, &str, bool)] = &[
+        ("*.txt", "a.txt", true),
+        ("*.txt", "a.md", false),
+        ("*.test.*", "a.test.ts", true),
+        ("*.test.*", "a.test.spec.ts", true),
+        ("*.test.*", "a.b.c", false),
+        ("a?c", "abc", true),
+        ("a?c", "ac", false),
+        ("*", "anything", true),
+        ("*", "", true),
+        ("", "", true),
+        ("", "a", false),
+        ("exact", "exact", true),
+        ("exact", "exactly", false),
+        ("*rm*", "rm -rf /", true),
+        ("**", "a/b", true),
+    ];
+
+    #[test]
+    fn glob_match_matches_table() {
+        for &(pattern, name, expected) in CASES {
+            assert_eq!(
+                glob_match(pattern, name),
+                expected,
+                "glob_match({pattern:?}, {name:?}) should be {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn glob_match_case_is_case_sensitive_by_default() {
+        assert!(!glob_match("*.TXT", "a.txt"));
+    }
+
+    #[test]
+    fn glob_match_case_insensitive_ignores_case() {
+        assert!(glob_match_case("*.TXT", "a.txt", false));
+        assert!(glob_match_case("*.amazonaws.com", "BUCKET.AMAZONAWS.COM", false));
+    }
+
+    fn workspace_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("tark-search-test-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn no_ignores() -> IgnoreRules {
+        IgnoreRules::load(&std::env::temp_dir(), &crate::config::WorkspaceConfig::default())
+    }
+
+    #[test]
+    fn find_files_caps_results_and_reports_truncation() {
+        let dir = workspace_dir("find-files-cap");
+        for i in 0..5 {
+            std::fs::write(dir.join(format!("f{i}.txt")), "x").unwrap();
+        }
+        let ignore = no_ignores();
+
+        let (matches, truncated) = find_files(&dir, "*.txt", &ignore, 100).unwrap();
+        assert_eq!(matches.len(), 5);
+        assert!(!truncated);
+
+        let (matches, truncated) = find_files(&dir, "*.txt", &ignore, 2).unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(truncated);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn search_content_finds_matches_with_line_column_and_context() {
+        let dir = workspace_dir("content-basic");
+        std::fs::write(dir.join("a.rs"), "fn one() {}\nfn two() {}\nfn three() {}\n").unwrap();
+        let ignore = no_ignores();
+
+        let result = search_content(&dir, r"fn two", &ignore, 100, 1).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        let m = &result.matches[0];
+        assert_eq!(m.line, 2);
+        assert_eq!(m.column, 1);
+        assert_eq!(m.context_before, vec!["fn one() {}".to_string()]);
+        assert_eq!(m.context_after, vec!["fn three() {}".to_string()]);
+        assert!(!result.truncated);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn search_content_caps_results_and_reports_truncation() {
+        let dir = workspace_dir("content-cap");
+        std::fs::write(dir.join("a.txt"), "needle\nneedle\nneedle\n").unwrap();
+        let ignore = no_ignores();
+
+        let result = search_content(&dir, "needle", &ignore, 2, 0).unwrap();
+        assert_eq!(result.matches.len(), 2);
+        assert!(result.truncated);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn search_content_skips_binary_files_instead_of_erroring() {
+        let dir = workspace_dir("content-binary");
+        std::fs::write(dir.join("a.bin"), [0xFFu8, 0x00, 0xFE, b'x']).unwrap();
+        std::fs::write(dir.join("b.txt"), "x marks the spot\n").unwrap();
+        let ignore = no_ignores();
+
+        let result = search_content(&dir, "x", &ignore, 100, 0).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].path.ends_with("b.txt"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn search_content_honors_ignore_rules() {
+        let dir = workspace_dir("content-ignore");
+        std::fs::write(dir.join("keep.txt"), "needle\n").unwrap();
+        std::fs::write(dir.join("skip.log"), "needle\n").unwrap();
+        let config = crate::config::WorkspaceConfig {
+            ignore_patterns: vec!["*.log".to_string()],
+        };
+        let ignore = IgnoreRules::load(&dir, &config);
+
+        let result = search_content(&dir, "needle", &ignore, 100, 0).unwrap();
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].path.ends_with("keep.txt"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn search_tool_content_mode_runs_off_the_runtime_thread_and_returns_context() {
+        let dir = workspace_dir("tool-content");
+        std::fs::write(dir.join("a.txt"), "before\nneedle here\nafter\n").unwrap();
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+        let ignore = no_ignores();
+        let tool = search_tool(sandbox, ignore);
+
+        let args = serde_json::json!({ "mode": "content", "query": "needle", "context_lines": 1 });
+        let result = (tool.handler)(args, tokio_util::sync::CancellationToken::new()).await.unwrap();
+
+        let matches = result["matches"].as_array().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["line"], 2);
+        assert_eq!(matches[0]["context_before"][0], "before");
+        assert_eq!(matches[0]["context_after"][0], "after");
+        assert_eq!(result["truncated"], false);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn search_tool_files_mode_still_works_unchanged() {
+        let dir = workspace_dir("tool-files");
+        std::fs::write(dir.join("a.txt"), "x").unwrap();
+        std::fs::write(dir.join("b.md"), "x").unwrap();
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+        let ignore = no_ignores();
+        let tool = search_tool(sandbox, ignore);
+
+        let args = serde_json::json!({ "mode": "files", "pattern": "*.txt" });
+        let result = (tool.handler)(args, tokio_util::sync::CancellationToken::new()).await.unwrap();
+
+        let matches = result["matches"].as_array().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].as_str().unwrap().ends_with("a.txt"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn search_tool_rejects_an_unknown_mode() {
+        let dir = workspace_dir("tool-bad-mode");
+        let sandbox = SandboxRoot::new(&dir).unwrap();
+        let ignore = no_ignores();
+        let tool = search_tool(sandbox, ignore);
+
+        let args = serde_json::json!({ "mode": "nonsense" });
+        let err = (tool.handler)(args, tokio_util::sync::CancellationToken::new()).await.unwrap_err();
+        assert!(err.to_string().contains("mode"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,345 @@
+//! `shell` tool: runs a command, streaming its stdout/stderr incrementally
+//! to a caller-supplied sink (the agent's stream callback, or a channel's
+//! tool-activity messages) instead of returning all output at once, since
+//! a long-running build or test otherwise shows nothing until it
+//! finishes. The full output is still captured for the tool result.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{AgentToolsConfig, TrustLevel};
+use crate::tools::registry::{RiskLevel, ToolDefinition};
+use crate::tools::sandbox::SandboxRoot;
+use crate::tools::search::glob_match;
+
+/// Caps the bytes forwarded to the stream sink so a command that logs
+/// megabytes of output can't flood a channel; the full output (subject to
+/// [`crate::tools::output_store`]'s own truncation) is still captured for
+/// the tool result regardless.
+const STREAM_CAP_BYTES: usize = 64 * 1024;
+
+/// Environment variables the sanitized shell environment always includes,
+/// regardless of `shell_env_allowlist` — without these, even trivial
+/// commands like `ls` or `git status` tend to misbehave.
+const DEFAULT_ENV_ALLOWLIST: &[&str] = &["PATH", "HOME", "LANG", "LC_ALL", "TERM", "TMPDIR", "USER", "SHELL", "PWD"];
+
+/// Substrings (checked case-insensitively) that flag a variable name as
+/// secret-looking. Applied even to names the user explicitly allowlisted,
+/// since an allowlist entry is usually written to let a *tool*-specific
+/// variable through, not to deliberately expose a credential.
+const SECRET_NAME_MARKERS: &[&str] = &["KEY", "SECRET", "TOKEN", "PASSWORD", "CREDENTIAL", "AUTH"];
+
+fn looks_like_secret(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_NAME_MARKERS.iter().any(|marker| upper.contains(marker))
+}
+
+/// Builds the environment the `shell` tool's child process should see.
+///
+/// `Manual` trust inherits the full host environment unchanged, since
+/// every command is shown to a human for approval before it runs. Any
+/// other trust level gets a sanitized environment: the
+/// [`DEFAULT_ENV_ALLOWLIST`] plus `config.shell_env_allowlist`, with
+/// anything matching [`looks_like_secret`] stripped even if allowlisted —
+/// the model can't exfiltrate a secret it never sees.
+pub fn sanitized_env(config: &AgentToolsConfig, trust: TrustLevel) -> HashMap<String, String> {
+    if trust == TrustLevel::Manual {
+        return std::env::vars().collect();
+    }
+    let allowed: HashSet<&str> = DEFAULT_ENV_ALLOWLIST
+        .iter()
+        .copied()
+        .chain(config.shell_env_allowlist.iter().map(|s| s.as_str()))
+        .collect();
+    std::env::vars()
+        .filter(|(name, _)| allowed.contains(name.as_str()) && !looks_like_secret(name))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShellOutputChunk {
+    pub stream: ShellStream,
+    pub line: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShellError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("command was cancelled")]
+    Cancelled,
+    #[error("cwd `{0}` is outside the sandbox root")]
+    CwdEscapesSandbox(PathBuf),
+    #[error("command changes directory to `{0}`, which is outside the sandbox root")]
+    CdEscapesSandbox(String),
+    #[error("`{0}` is on shell_blocked and may not be run")]
+    Blocked(String),
+}
+
+/// Splits `command` into top-level segments on `;`, `&&`, `||` and
+/// newlines. This is a best-effort lexical scan, not a real shell parse —
+/// good enough for the sandbox/matcher checks below without pulling in a
+/// shell grammar.
+fn command_segments(command: &str) -> impl Iterator<Item = &str> {
+    command
+        .split(['\n', ';'])
+        .flat_map(|segment| segment.split("&&"))
+        .flat_map(|segment| segment.split("||"))
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+}
+
+/// Finds the target of every top-level `cd` in `command` (see
+/// [`command_segments`]).
+fn cd_targets(command: &str) -> Vec<&str> {
+    command_segments(command)
+        .filter_map(|segment| segment.strip_prefix("cd "))
+        .map(str::trim)
+        .collect()
+}
+
+/// Extracts the leading program name of a single command segment,
+/// skipping any `VAR=value` environment-variable prefixes (`FOO=bar curl
+/// ...`) and stripping surrounding quotes (`'curl' ...`). Not a full shell
+/// parse, but enough to identify what's actually being invoked for
+/// [`classify_shell_command`].
+fn leading_program(segment: &str) -> Option<String> {
+    let mut tokens = segment.split_whitespace();
+    let mut token = tokens.next()?;
+    while is_env_assignment(token) {
+        token = tokens.next()?;
+    }
+    Some(token.trim_matches(['\'', '"']).to_string())
+}
+
+fn is_env_assignment(token: &str) -> bool {
+    match token.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty()
+                && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// The outcome of checking a shell command against
+/// `config.agent_tools.shell_blocked`/`shell_always_ask`, independent of
+/// (and consulted before) any remembered [`crate::approval::ApprovalPattern`] —
+/// a command on `shell_blocked` is refused even if the user previously
+/// approved it forever, and one on `shell_always_ask` always prompts even
+/// at a trust level that would otherwise skip the approval flow entirely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellCommandVerdict {
+    Blocked(String),
+    AlwaysAsk(String),
+    Normal,
+}
+
+/// Classifies `command` by checking every top-level segment's leading
+/// program name (see [`leading_program`]) against `config`'s pattern
+/// lists. `shell_blocked` takes priority over `shell_always_ask` when a
+/// command matches both.
+pub fn classify_shell_command(command: &str, config: &AgentToolsConfig) -> ShellCommandVerdict {
+    let programs: Vec<String> = command_segments(command).filter_map(leading_program).collect();
+    if let Some(program) = programs
+        .iter()
+        .find(|program| config.shell_blocked.iter().any(|pattern| glob_match(pattern, program)))
+    {
+        return ShellCommandVerdict::Blocked(program.clone());
+    }
+    if let Some(program) = programs
+        .iter()
+        .find(|program| config.shell_always_ask.iter().any(|pattern| glob_match(pattern, program)))
+    {
+        return ShellCommandVerdict::AlwaysAsk(program.clone());
+    }
+    ShellCommandVerdict::Normal
+}
+
+#[derive(Debug, Clone)]
+pub struct ShellResult {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    /// `true` if streamed output was capped before the command finished
+    /// (the captured `stdout`/`stderr` are unaffected).
+    pub truncated: bool,
+}
+
+/// Runs `command` in a shell under `cwd` (already confined to `sandbox`
+/// by the caller) with `env` as its complete environment (see
+/// [`sanitized_env`]), invoking `on_output` for each line of stdout/stderr
+/// as it arrives (capped at [`STREAM_CAP_BYTES`]) while still accumulating
+/// the full output into the result. Rejects the command outright if it
+/// contains a `cd` to somewhere outside `sandbox` (see [`cd_targets`]).
+/// Killing the child when `cancel` fires lets the interrupt flag stop a
+/// long-running command instead of waiting it out.
+pub async fn run_shell_streaming(
+    command: &str,
+    cwd: &Path,
+    sandbox: &SandboxRoot,
+    env: &HashMap<String, String>,
+    cancel: CancellationToken,
+    mut on_output: impl FnMut(ShellOutputChunk) + Send,
+) -> Result<ShellResult, ShellError> {
+    for target in cd_targets(command) {
+        if sandbox.resolve_from(cwd, Path::new(target)).is_err() {
+            return Err(ShellError::CdEscapesSandbox(target.to_string()));
+        }
+    }
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .env_clear()
+        .envs(env)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdout_lines = BufReader::new(child.stdout.take().expect("stdout is piped")).lines();
+    let mut stderr_lines = BufReader::new(child.stderr.take().expect("stderr is piped")).lines();
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let mut streamed_bytes = 0usize;
+    let mut truncated = false;
+    let mut stdout_done = false;
+    let mut stderr_done = false;
+
+    loop {
+        if stdout_done && stderr_done {
+            break;
+        }
+        tokio::select! {
+            _ = cancel.cancelled() => {
+                let _ = child.kill().await;
+                return Err(ShellError::Cancelled);
+            }
+            line = stdout_lines.next_line(), if !stdout_done => {
+                match line? {
+                    Some(line) => {
+                        stdout.push_str(&line);
+                        stdout.push('\n');
+                        if streamed_bytes < STREAM_CAP_BYTES {
+                            streamed_bytes += line.len();
+                            on_output(ShellOutputChunk { stream: ShellStream::Stdout, line });
+                        } else {
+                            truncated = true;
+                        }
+                    }
+                    None => stdout_done = true,
+                }
+            }
+            line = stderr_lines.next_line(), if !stderr_done => {
+                match line? {
+                    Some(line) => {
+                        stderr.push_str(&line);
+                        stderr.push('\n');
+                        if streamed_bytes < STREAM_CAP_BYTES {
+                            streamed_bytes += line.len();
+                            on_output(ShellOutputChunk { stream: ShellStream::Stderr, line });
+                        } else {
+                            truncated = true;
+                        }
+                    }
+                    None => stderr_done = true,
+                }
+            }
+        }
+    }
+
+    let status = child.wait().await?;
+    Ok(ShellResult {
+        stdout,
+        stderr,
+        exit_code: status.code(),
+        truncated,
+    })
+}
+
+/// Builds the `shell` tool, forwarding streamed output lines to
+/// `on_output` as the command runs. `default_cwd` must already be inside
+/// `sandbox`; it's used when a call omits the `cwd` argument, and a call
+/// that supplies one gets it resolved via `sandbox.resolve_from` so a
+/// relative path is confined the same way file tools already are. The
+/// child's environment is computed once via [`sanitized_env`] from
+/// `tools_config`/`trust` at build time rather than per-call, since
+/// neither changes within a session. `tools_config.shell_blocked` is
+/// re-checked on every call as a backstop — see
+/// [`classify_shell_command`] for the approval-gate half of this (checked
+/// by the caller before the command ever reaches this tool).
+pub fn shell_tool(
+    default_cwd: PathBuf,
+    sandbox: SandboxRoot,
+    tools_config: AgentToolsConfig,
+    trust: TrustLevel,
+    on_output: impl Fn(ShellOutputChunk) + Send + Sync + 'static,
+) -> ToolDefinition {
+    let on_output: Arc<dyn Fn(ShellOutputChunk) + Send + Sync> = Arc::new(on_output);
+    let env = sanitized_env(&tools_config, trust);
+    let sandbox = Arc::new(sandbox);
+    ToolDefinition {
+        name: "shell".to_string(),
+        risk: crate::tools::tools_db::classify("shell")
+            .map(|c| c.risk)
+            .unwrap_or(RiskLevel::Destructive),
+        timeout: Duration::from_secs(300),
+        input_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "command": {"type": "string"},
+                "cwd": {"type": "string"},
+            },
+            "required": ["command"],
+        })),
+        handler: Box::new(move |args, cancel| {
+            let default_cwd = default_cwd.clone();
+            let env = env.clone();
+            let on_output = on_output.clone();
+            let sandbox = sandbox.clone();
+            let tools_config = tools_config.clone();
+            Box::pin(async move {
+                let command = args
+                    .get("command")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("shell tool requires a `command` argument"))?;
+                // `shell_always_ask` is enforced upstream of this handler,
+                // at the approval gate; `shell_blocked` is enforced again
+                // here as a hard backstop, in case a caller invokes this
+                // tool directly without going through approval at all.
+                if let ShellCommandVerdict::Blocked(program) = classify_shell_command(command, &tools_config) {
+                    return Err(ShellError::Blocked(program).into());
+                }
+                let cwd = match args.get("cwd").and_then(|v| v.as_str()) {
+                    Some(requested) => sandbox
+                        .resolve_from(&default_cwd, Path::new(requested))
+                        .map_err(|_| ShellError::CwdEscapesSandbox(PathBuf::from(requested)))?,
+                    None => default_cwd,
+                };
+                let result = run_shell_streaming(command, &cwd, &sandbox, &env, cancel, move |chunk| on_output(chunk)).await?;
+                Ok(serde_json::json!({
+                    "stdout": result.stdout,
+                    "stderr": result.stderr,
+                    "exit_code": result.exit_code,
+                    "truncated": result.truncated,
+                }))
+            })
+        }),
+    }
+}
@@ -0,0 +1,100 @@
+//! Central classification of what each tool is allowed to do: its
+//! [`RiskLevel`] (how much user attention a call deserves) and its
+//! [`SideEffect`] (what kind of change it can make). Previously this
+//! knowledge was scattered across each tool's own registration call;
+//! collecting it here lets the approval system, trust-level gating, and
+//! parallel dispatch all agree on the same answer for a given tool name.
+
+use crate::tools::registry::RiskLevel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideEffect {
+    /// Never changes anything outside the process; safe to run
+    /// concurrently with other read-only calls.
+    ReadOnly,
+    /// Changes local state (files, git history, stored config, ...).
+    Mutating,
+    /// Talks to the network, even if it doesn't mutate local state —
+    /// called out separately since it has its own failure modes (SSRF,
+    /// flaky connectivity, rate limits) that a purely local read doesn't.
+    Network,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ToolClassification {
+    pub risk: RiskLevel,
+    pub side_effect: SideEffect,
+}
+
+/// Looks up the classification for a built-in tool by name. Plugin-
+/// provided tools aren't in this table — they declare their own
+/// classification in the plugin manifest, and callers should use
+/// [`classify_with_override`] so a manifest-declared classification always
+/// wins over this table's default for the same name.
+pub fn classify(tool_name: &str) -> Option<ToolClassification> {
+    use RiskLevel::{Destructive, Mutating, ReadOnly};
+    use SideEffect::Network;
+
+    let (risk, side_effect) = match tool_name {
+        "read_file" | "search" | "git_context" | "fetch_tool_output" => (ReadOnly, SideEffect::ReadOnly),
+        "edit_file" | "write_file" => (Mutating, SideEffect::Mutating),
+        "shell" => (Destructive, SideEffect::Mutating),
+        "web_fetch" => (Mutating, Network),
+        _ => return None,
+    };
+    Some(ToolClassification { risk, side_effect })
+}
+
+/// Resolves a tool's classification, preferring `plugin_declared` (from
+/// the plugin's own manifest) over this table's built-in default.
+pub fn classify_with_override(
+    tool_name: &str,
+    plugin_declared: Option<ToolClassification>,
+) -> Option<ToolClassification> {
+    plugin_declared.or_else(|| classify(tool_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_every_built_in_tool() {
+        for name in ["read_file", "search", "git_context", "fetch_tool_output", "edit_file", "write_file", "shell", "web_fetch"] {
+            assert!(classify(name).is_some(), "expected a classification for `{name}`");
+        }
+    }
+
+    #[test]
+    fn unknown_tool_names_are_not_classified() {
+        assert!(classify("definitely_not_a_real_tool").is_none());
+    }
+
+    #[test]
+    fn web_fetch_is_flagged_as_a_network_side_effect() {
+        let classification = classify("web_fetch").unwrap();
+        assert_eq!(classification.side_effect, SideEffect::Network);
+    }
+
+    #[test]
+    fn read_only_tools_report_the_read_only_side_effect() {
+        let classification = classify("read_file").unwrap();
+        assert_eq!(classification.side_effect, SideEffect::ReadOnly);
+    }
+
+    #[test]
+    fn plugin_declared_classification_overrides_the_built_in_table() {
+        let plugin_declared = ToolClassification {
+            risk: crate::tools::registry::RiskLevel::Destructive,
+            side_effect: SideEffect::Network,
+        };
+        let resolved = classify_with_override("read_file", Some(plugin_declared)).unwrap();
+        assert_eq!(resolved.side_effect, SideEffect::Network);
+    }
+
+    #[test]
+    fn falls_back_to_the_built_in_table_when_the_plugin_declares_nothing() {
+        let resolved = classify_with_override("read_file", None).unwrap();
+        assert_eq!(resolved.side_effect, SideEffect::ReadOnly);
+    }
+}
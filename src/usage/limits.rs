@@ -0,0 +1,103 @@
+//! Per-session and global spend limits that halt further requests once
+//! exceeded.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SpendLimitError {
+    #[error("session spend limit of ${limit:.2} exceeded (spent ${spent:.2})")]
+    SessionLimitExceeded { spent: f64, limit: f64 },
+    #[error("global spend limit of ${limit:.2} exceeded (spent ${spent:.2})")]
+    GlobalLimitExceeded { spent: f64, limit: f64 },
+}
+
+/// Tracks cumulative spend in micro-dollars (integer, avoids float
+/// accumulation drift) against optional per-session and global caps.
+pub struct SpendLimiter {
+    session_limit_usd: Option<f64>,
+    global_limit_usd: Option<f64>,
+    global_spent_micros: AtomicU64,
+    session_spent_micros: Mutex<std::collections::HashMap<String, u64>>,
+}
+
+impl SpendLimiter {
+    pub fn new(session_limit_usd: Option<f64>, global_limit_usd: Option<f64>) -> Self {
+        Self {
+            session_limit_usd,
+            global_limit_usd,
+            global_spent_micros: AtomicU64::new(0),
+            session_spent_micros: Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+
+    /// Check whether `session_id` may spend `additional_usd` more without
+    /// breaching either limit. Does not record the spend; call
+    /// [`SpendLimiter::record`] after the request succeeds.
+    pub fn check(&self, session_id: &str, additional_usd: f64) -> Result<(), SpendLimitError> {
+        if let Some(limit) = self.global_limit_usd {
+            let spent = self.global_spent_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+            if spent + additional_usd > limit {
+                return Err(SpendLimitError::GlobalLimitExceeded { spent, limit });
+            }
+        }
+        if let Some(limit) = self.session_limit_usd {
+            let sessions = self.session_spent_micros.lock().unwrap();
+            let spent = *sessions.get(session_id).unwrap_or(&0) as f64 / 1_000_000.0;
+            if spent + additional_usd > limit {
+                return Err(SpendLimitError::SessionLimitExceeded { spent, limit });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&self, session_id: &str, amount_usd: f64) {
+        let micros = (amount_usd * 1_000_000.0).round() as u64;
+        self.global_spent_micros.fetch_add(micros, Ordering::Relaxed);
+        let mut sessions = self.session_spent_micros.lock().unwrap();
+        *sessions.entry(session_id.to_string()).or_insert(0) += micros;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_when_no_caps_are_configured() {
+        let limiter = SpendLimiter::new(None, None);
+        limiter.record("session-a", 1_000_000.0);
+        assert!(limiter.check("session-a", 1_000_000.0).is_ok());
+    }
+
+    #[test]
+    fn rejects_once_the_session_limit_would_be_exceeded() {
+        let limiter = SpendLimiter::new(Some(10.0), None);
+        limiter.record("session-a", 9.0);
+        assert!(limiter.check("session-a", 1.0).is_ok());
+        limiter.record("session-a", 1.0);
+        assert!(matches!(
+            limiter.check("session-a", 0.01),
+            Err(SpendLimitError::SessionLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn session_limit_is_tracked_independently_per_session() {
+        let limiter = SpendLimiter::new(Some(10.0), None);
+        limiter.record("session-a", 10.0);
+        assert!(limiter.check("session-a", 0.01).is_err());
+        assert!(limiter.check("session-b", 0.01).is_ok());
+    }
+
+    #[test]
+    fn global_limit_applies_across_all_sessions() {
+        let limiter = SpendLimiter::new(None, Some(10.0));
+        limiter.record("session-a", 6.0);
+        limiter.record("session-b", 4.0);
+        assert!(matches!(
+            limiter.check("session-b", 0.01),
+            Err(SpendLimitError::GlobalLimitExceeded { .. })
+        ));
+    }
+}
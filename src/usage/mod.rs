@@ -0,0 +1,162 @@
+//! Usage tracking: per-request token/cost logging and summarization.
+
+pub mod limits;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::PricingConfig;
+use crate::llm::models_db::ModelsDb;
+use crate::llm::TokenUsage;
+
+/// One logged request, as written by `UsageTracker`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub session_id: String,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageSummary {
+    pub total_requests: u64,
+    pub total_input_tokens: u64,
+    pub total_output_tokens: u64,
+    pub total_cost_usd: f64,
+    pub by_model: std::collections::BTreeMap<String, ModelUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelUsage {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Aggregates usage records (e.g. from [`read_records`]) into a summary,
+/// optionally restricted to a single session.
+pub fn summarize(records: impl IntoIterator<Item = UsageRecord>, session_id: Option<&str>) -> UsageSummary {
+    let mut summary = UsageSummary::default();
+    for record in records {
+        if let Some(session_id) = session_id {
+            if record.session_id != session_id {
+                continue;
+            }
+        }
+        summary.total_requests += 1;
+        summary.total_input_tokens += record.input_tokens;
+        summary.total_output_tokens += record.output_tokens;
+        summary.total_cost_usd += record.cost_usd;
+
+        let entry = summary.by_model.entry(record.model).or_default();
+        entry.requests += 1;
+        entry.input_tokens += record.input_tokens;
+        entry.output_tokens += record.output_tokens;
+        entry.cost_usd += record.cost_usd;
+    }
+    summary
+}
+
+/// Appends `UsageRecord`s to an NDJSON log and prices them from
+/// [`ModelsDb`]'s models.dev pricing.
+pub struct UsageTracker {
+    log_path: PathBuf,
+    models_db: Arc<ModelsDb>,
+    pricing: PricingConfig,
+}
+
+impl UsageTracker {
+    pub fn new(log_path: impl Into<PathBuf>, models_db: Arc<ModelsDb>, pricing: PricingConfig) -> Self {
+        Self {
+            log_path: log_path.into(),
+            models_db,
+            pricing,
+        }
+    }
+
+    /// Prices `input_tokens`/`output_tokens`, preferring a configured
+    /// [`PricingConfig`] override, then `models_db`'s models.dev rate for
+    /// `provider`/`model`, then `0.0` (e.g. for local/self-hosted models
+    /// neither prices — the user can add an explicit override for
+    /// electricity/compute cost if they want one).
+    pub fn calculate_cost(&self, provider: &str, model: &str, input_tokens: u64, output_tokens: u64) -> f64 {
+        let price = self.pricing.get(provider, model).unwrap_or_else(|| {
+            self.models_db
+                .capabilities(provider, model)
+                .map(|c| crate::config::ModelPrice {
+                    input_cost_per_1m: c.input_cost_per_1m,
+                    output_cost_per_1m: c.output_cost_per_1m,
+                })
+                .unwrap_or_default()
+        });
+        (input_tokens as f64 / 1_000_000.0) * price.input_cost_per_1m
+            + (output_tokens as f64 / 1_000_000.0) * price.output_cost_per_1m
+    }
+
+    /// Appends `record` as one NDJSON line.
+    pub fn record(&self, record: &UsageRecord) -> std::io::Result<()> {
+        use std::io::Write;
+        if let Some(parent) = self.log_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.log_path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)
+    }
+
+    /// Reads every record logged so far, for `tark usage` and
+    /// [`summarize`].
+    pub fn read_records(&self) -> std::io::Result<Vec<UsageRecord>> {
+        read_records(&self.log_path)
+    }
+}
+
+/// Reads an NDJSON usage log, skipping lines that fail to parse (e.g. a
+/// partially written final line from a crash).
+pub fn read_records(log_path: &Path) -> std::io::Result<Vec<UsageRecord>> {
+    if !log_path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(log_path)?;
+    Ok(contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Turns a completed turn's [`TokenUsage`] into a logged [`UsageRecord`]
+/// and a [`crate::channels::interactions::RemoteEvent::Usage`], so a
+/// channel can show an accurate cost line for a turn even when the
+/// provider only reported usage at the end of a stream.
+pub fn apply_usage(
+    tracker: &UsageTracker,
+    session_id: &str,
+    channel_id: &str,
+    provider: &str,
+    model: &str,
+    usage: &TokenUsage,
+) -> crate::channels::interactions::RemoteEvent {
+    let cost_usd = tracker.calculate_cost(provider, model, usage.input_tokens, usage.output_tokens);
+    let _ = tracker.record(&UsageRecord {
+        session_id: session_id.to_string(),
+        provider: provider.to_string(),
+        model: model.to_string(),
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cost_usd,
+    });
+    crate::channels::interactions::RemoteEvent::Usage {
+        channel_id: channel_id.to_string(),
+        session_id: session_id.to_string(),
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cost_usd,
+        estimated: usage.estimated,
+    }
+}